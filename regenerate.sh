@@ -1,12 +1,74 @@
 #!/usr/bin/env bash
 
+# Regenerate the per-architecture bindings in `src/bindings/`.
+#
+# Usage: regenerate.sh HEADERS [ARCH...]
+#
+# HEADERS is a directory containing one sanitized kernel header tree per
+# kernel architecture, as produced by running the following in a Linux
+# source tree for each of x86, arm64, arm, riscv, powerpc and s390:
+#
+#     make headers_install ARCH=$karch INSTALL_HDR_PATH=$HEADERS/$karch
+#
+# If no ARCH arguments are given, all supported architectures are
+# regenerated. ARCH names are Rust `target_arch` values, except that
+# little-endian 64-bit PowerPC is `powerpc64le`.
+#
+# This needs bindgen 0.69.4 or later, for `--rust-target 1.77`.
+
 set -eu
 
 cd $(dirname $0)
 
-(
-    sed -e '/automatically generated by rust-bindgen/,$d' src/bindings.rs
-    bindgen --with-derive-default wrapper.h
-) > new-bindings.rs~
+headers=$(realpath "$1")
+shift
+
+if [ $# -eq 0 ]; then
+    set -- x86_64 x86 aarch64 arm riscv64 powerpc64le s390x
+fi
+
+for arch in "$@"; do
+    # The kernel's name for the architecture, and a clang target triple.
+    case $arch in
+        x86_64)      karch=x86;     triple=x86_64-unknown-linux-gnu ;;
+        x86)         karch=x86;     triple=i686-unknown-linux-gnu ;;
+        aarch64)     karch=arm64;   triple=aarch64-unknown-linux-gnu ;;
+        arm)         karch=arm;     triple=armv7-unknown-linux-gnueabihf ;;
+        riscv64)     karch=riscv;   triple=riscv64gc-unknown-linux-gnu ;;
+        powerpc64le) karch=powerpc; triple=powerpc64le-unknown-linux-gnu ;;
+        s390x)       karch=s390;    triple=s390x-unknown-linux-gnu ;;
+        *) echo "unsupported architecture: $arch" >&2; exit 1 ;;
+    esac
+
+    (
+        cat <<PREAMBLE
+//! Bindings generated from the \`$arch\` kernel headers.
+//!
+//! Generated by \`regenerate.sh\`; see the \`bindings\` module for details.
+
+#![allow(dead_code)]
+#![allow(non_upper_case_globals)]
+#![allow(non_camel_case_types)]
+#![allow(non_snake_case)]
+#![allow(clippy::missing_safety_doc)]
+#![allow(clippy::too_many_arguments)]
+#![allow(clippy::unnecessary_cast)]
+#![allow(clippy::useless_transmute)]
+
+PREAMBLE
+        # With a Rust target of 1.77 or later, bindgen writes its layout
+        # tests with `offset_of!`, rather than by dereferencing a null
+        # pointer, which aborts under debug assertions.
+        bindgen --with-derive-default --rust-target 1.77 wrapper.h -- \
+                --target=$triple -nostdinc -I"$headers/$karch/include"
+    ) > src/bindings/$arch.rs~
+
+    # The register index enums from <asm/perf_regs.h>, which the `regs`
+    # module documents, must come through on every architecture.
+    if ! grep -q '^pub type perf_event_[a-z0-9]*_regs = ' src/bindings/$arch.rs~; then
+        echo "$arch: no perf_event_*_regs enum in the generated bindings" >&2
+        exit 1
+    fi
 
-mv new-bindings.rs~ src/bindings.rs
+    mv src/bindings/$arch.rs~ src/bindings/$arch.rs
+done
@@ -0,0 +1,248 @@
+//! Typed accessors for `perf_event_attr`'s bitfields and anonymous unions.
+//!
+//! `bindgen` reflects the flag bits of `perf_event_attr` as methods that take
+//! and return `__u64`, and its anonymous unions as fields named
+//! `__bindgen_anon_1` through `__bindgen_anon_4`. The numbering of those
+//! fields is an artifact of the order in which `bindgen` encounters them, so
+//! code that mentions them directly tends to break when the bindings are
+//! regenerated.
+//!
+//! This module adds inherent methods to [`perf_event_attr`] with stable names:
+//!
+//! -   For each single-bit flag `foo`, a `foo_flag` getter returning `bool`
+//!     and a `set_foo_flag` setter taking `bool`. (The bare names `foo` and
+//!     `set_foo` are taken by `bindgen`'s own accessors.)
+//!
+//! -   For the two-bit `precise_ip` field, [`precise_ip_level`] and
+//!     [`set_precise_ip_level`].
+//!
+//! -   For each member `bar` of an anonymous union, a `bar` getter and a
+//!     `set_bar` setter, named after the C member.
+//!
+//! For example:
+//!
+//! ```
+//! use perf_event_open_sys::bindings;
+//!
+//! let mut attrs = bindings::perf_event_attr::default();
+//! attrs.set_disabled_flag(true);
+//! attrs.set_exclude_kernel_flag(true);
+//! attrs.set_sample_period(10_000);
+//! attrs.set_config1(0x1234);
+//!
+//! assert!(attrs.disabled_flag());
+//! assert!(!attrs.exclude_hv_flag());
+//! assert_eq!(attrs.sample_freq(), 10_000); // shares storage with sample_period
+//! assert_eq!(attrs.config1(), 0x1234);
+//! ```
+//!
+//! [`perf_event_attr`]: crate::bindings::perf_event_attr
+//! [`precise_ip_level`]: crate::bindings::perf_event_attr::precise_ip_level
+//! [`set_precise_ip_level`]: crate::bindings::perf_event_attr::set_precise_ip_level
+
+use crate::bindings::perf_event_attr;
+
+macro_rules! define_flags {
+    ( $( $args:tt )* ) => {
+        impl perf_event_attr {
+            $(
+                define_flag!($args);
+            )*
+        }
+    }
+}
+
+macro_rules! define_flag {
+    ({ $get:ident, $set:ident, $raw_get:ident, $raw_set:ident, $doc:literal }) => {
+        #[doc = concat!("Return the `", stringify!($raw_get), "` bit: ", $doc)]
+        #[inline]
+        pub fn $get(&self) -> bool {
+            self.$raw_get() != 0
+        }
+
+        #[doc = concat!("Set the `", stringify!($raw_get), "` bit: ", $doc)]
+        #[inline]
+        pub fn $set(&mut self, value: bool) {
+            self.$raw_set(value as u64)
+        }
+    };
+}
+
+define_flags! {
+    { disabled_flag, set_disabled_flag, disabled, set_disabled,
+      "the counter starts out disabled." }
+    { inherit_flag, set_inherit_flag, inherit, set_inherit,
+      "children inherit the counter." }
+    { pinned_flag, set_pinned_flag, pinned, set_pinned,
+      "the counter must always be on the PMU." }
+    { exclusive_flag, set_exclusive_flag, exclusive, set_exclusive,
+      "the counter's group is the only group on the PMU." }
+    { exclude_user_flag, set_exclude_user_flag, exclude_user, set_exclude_user,
+      "don't count events in user space." }
+    { exclude_kernel_flag, set_exclude_kernel_flag, exclude_kernel, set_exclude_kernel,
+      "don't count events in the kernel." }
+    { exclude_hv_flag, set_exclude_hv_flag, exclude_hv, set_exclude_hv,
+      "don't count events in the hypervisor." }
+    { exclude_idle_flag, set_exclude_idle_flag, exclude_idle, set_exclude_idle,
+      "don't count when the CPU is idle." }
+    { mmap_flag, set_mmap_flag, mmap, set_mmap,
+      "record executable `mmap` events." }
+    { comm_flag, set_comm_flag, comm, set_comm,
+      "record process name changes." }
+    { freq_flag, set_freq_flag, freq, set_freq,
+      "sample by frequency, not period." }
+    { inherit_stat_flag, set_inherit_stat_flag, inherit_stat, set_inherit_stat,
+      "save inherited counts per task." }
+    { enable_on_exec_flag, set_enable_on_exec_flag, enable_on_exec, set_enable_on_exec,
+      "enable the counter on the next `exec`." }
+    { task_flag, set_task_flag, task, set_task,
+      "record fork and exit events." }
+    { watermark_flag, set_watermark_flag, watermark, set_watermark,
+      "wake up by `wakeup_watermark` bytes, not `wakeup_events` events." }
+    { mmap_data_flag, set_mmap_data_flag, mmap_data, set_mmap_data,
+      "record non-executable `mmap` events." }
+    { sample_id_all_flag, set_sample_id_all_flag, sample_id_all, set_sample_id_all,
+      "append `sample_type` identification to all records." }
+    { exclude_host_flag, set_exclude_host_flag, exclude_host, set_exclude_host,
+      "don't count in the host." }
+    { exclude_guest_flag, set_exclude_guest_flag, exclude_guest, set_exclude_guest,
+      "don't count in guests." }
+    { exclude_callchain_kernel_flag, set_exclude_callchain_kernel_flag,
+      exclude_callchain_kernel, set_exclude_callchain_kernel,
+      "omit kernel frames from callchains." }
+    { exclude_callchain_user_flag, set_exclude_callchain_user_flag,
+      exclude_callchain_user, set_exclude_callchain_user,
+      "omit user frames from callchains." }
+    { mmap2_flag, set_mmap2_flag, mmap2, set_mmap2,
+      "record `mmap` events with inode data." }
+    { comm_exec_flag, set_comm_exec_flag, comm_exec, set_comm_exec,
+      "flag process name changes due to `exec`." }
+    { use_clockid_flag, set_use_clockid_flag, use_clockid, set_use_clockid,
+      "use `clockid` for time fields." }
+    { context_switch_flag, set_context_switch_flag, context_switch, set_context_switch,
+      "record context switches." }
+    { write_backward_flag, set_write_backward_flag, write_backward, set_write_backward,
+      "write the ring buffer from end to beginning." }
+    { namespaces_flag, set_namespaces_flag, namespaces, set_namespaces,
+      "record namespace events." }
+    { ksymbol_flag, set_ksymbol_flag, ksymbol, set_ksymbol,
+      "record kernel symbol events." }
+    { bpf_event_flag, set_bpf_event_flag, bpf_event, set_bpf_event,
+      "record BPF program load and unload events." }
+    { aux_output_flag, set_aux_output_flag, aux_output, set_aux_output,
+      "generate AUX records instead of events." }
+    { cgroup_flag, set_cgroup_flag, cgroup, set_cgroup,
+      "record cgroup creation events." }
+    { text_poke_flag, set_text_poke_flag, text_poke, set_text_poke,
+      "record kernel text modification events." }
+    { build_id_flag, set_build_id_flag, build_id, set_build_id,
+      "use build ids in `mmap2` events." }
+    { inherit_thread_flag, set_inherit_thread_flag, inherit_thread, set_inherit_thread,
+      "children inherit the counter only if cloned with `CLONE_THREAD`." }
+    { remove_on_exec_flag, set_remove_on_exec_flag, remove_on_exec, set_remove_on_exec,
+      "remove the event from the task on `exec`." }
+    { sigtrap_flag, set_sigtrap_flag, sigtrap, set_sigtrap,
+      "send a synchronous `SIGTRAP` when the event overflows." }
+}
+
+macro_rules! define_union_members {
+    ( $( $args:tt )* ) => {
+        impl perf_event_attr {
+            $(
+                define_union_member!($args);
+            )*
+        }
+    }
+}
+
+macro_rules! define_union_member {
+    ({ $union:ident, $get:ident, $set:ident, $ty:ty }) => {
+        #[doc = concat!("Return the `", stringify!($get), "` member of its anonymous union.")]
+        #[inline]
+        pub fn $get(&self) -> $ty {
+            // All members of these unions are plain integers of the same
+            // size, so any bit pattern is a valid value of any of them.
+            unsafe { self.$union.$get }
+        }
+
+        #[doc = concat!("Set the `", stringify!($get), "` member of its anonymous union.")]
+        #[inline]
+        pub fn $set(&mut self, value: $ty) {
+            self.$union.$get = value;
+        }
+    };
+}
+
+define_union_members! {
+    { __bindgen_anon_1, sample_period, set_sample_period, u64 }
+    { __bindgen_anon_1, sample_freq, set_sample_freq, u64 }
+
+    { __bindgen_anon_2, wakeup_events, set_wakeup_events, u32 }
+    { __bindgen_anon_2, wakeup_watermark, set_wakeup_watermark, u32 }
+
+    { __bindgen_anon_3, bp_addr, set_bp_addr, u64 }
+    { __bindgen_anon_3, kprobe_func, set_kprobe_func, u64 }
+    { __bindgen_anon_3, uprobe_path, set_uprobe_path, u64 }
+    { __bindgen_anon_3, config1, set_config1, u64 }
+
+    { __bindgen_anon_4, bp_len, set_bp_len, u64 }
+    { __bindgen_anon_4, kprobe_addr, set_kprobe_addr, u64 }
+    { __bindgen_anon_4, probe_offset, set_probe_offset, u64 }
+    { __bindgen_anon_4, config2, set_config2, u64 }
+}
+
+impl perf_event_attr {
+    /// Return the `precise_ip` skid constraint, from 0 to 3.
+    ///
+    /// -   0: `SAMPLE_IP` can have arbitrary skid
+    /// -   1: `SAMPLE_IP` must have constant skid
+    /// -   2: `SAMPLE_IP` requested to have 0 skid
+    /// -   3: `SAMPLE_IP` must have 0 skid
+    #[inline]
+    pub fn precise_ip_level(&self) -> u8 {
+        self.precise_ip() as u8
+    }
+
+    /// Set the `precise_ip` skid constraint. Only the low two bits of `level`
+    /// are used.
+    #[inline]
+    pub fn set_precise_ip_level(&mut self, level: u8) {
+        self.set_precise_ip((level & 0b11) as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bindings::perf_event_attr;
+
+    #[test]
+    fn flags_are_independent() {
+        let mut attrs = perf_event_attr::default();
+        attrs.set_exclude_kernel_flag(true);
+        attrs.set_precise_ip_level(2);
+        attrs.set_sigtrap_flag(true);
+        assert!(attrs.exclude_kernel_flag());
+        assert!(attrs.sigtrap_flag());
+        assert!(!attrs.exclude_user_flag());
+        assert!(!attrs.remove_on_exec_flag());
+        assert_eq!(attrs.precise_ip_level(), 2);
+        assert_eq!(attrs.exclude_kernel(), 1);
+
+        attrs.set_exclude_kernel_flag(false);
+        assert!(!attrs.exclude_kernel_flag());
+        assert!(attrs.sigtrap_flag());
+    }
+
+    #[test]
+    fn union_members_alias() {
+        let mut attrs = perf_event_attr::default();
+        attrs.set_wakeup_watermark(4096);
+        assert_eq!(attrs.wakeup_events(), 4096);
+        attrs.set_kprobe_func(0xdead_beef);
+        assert_eq!(attrs.bp_addr(), 0xdead_beef);
+        assert_eq!(attrs.config1(), 0xdead_beef);
+        attrs.set_probe_offset(16);
+        assert_eq!(attrs.config2(), 16);
+        assert_eq!(attrs.bp_len(), 16);
+    }
+}
@@ -0,0 +1,250 @@
+//! Hardware breakpoints and watchpoints.
+//!
+//! A `PERF_TYPE_BREAKPOINT` event counts executions of an instruction, or
+//! accesses to a range of memory, using the processor's debug registers. The
+//! location is given by the `bp_type`, `bp_addr` and `bp_len` fields of
+//! `perf_event_attr`, and the kernel rejects, with `EINVAL`, combinations the
+//! hardware can't watch:
+//!
+//! -   Execute breakpoints must have a length of `sizeof(long)`.
+//!
+//! -   Data watchpoints must have a length of 1, 2, 4, or 8 bytes, and the
+//!     address must be aligned to that length.
+//!
+//! A [`Breakpoint`] checks these rules before producing the attr:
+//!
+//! ```
+//! use perf_event_open_sys::bindings;
+//! use perf_event_open_sys::breakpoint::{Breakpoint, BreakpointError};
+//!
+//! let attrs = Breakpoint::write(0x1000, 8).attr()?;
+//! assert_eq!(attrs.type_, bindings::perf_type_id_PERF_TYPE_BREAKPOINT);
+//! assert_eq!(attrs.bp_type, bindings::HW_BREAKPOINT_W);
+//!
+//! assert_eq!(
+//!     Breakpoint::write(0x1004, 8).attr().err(),
+//!     Some(BreakpointError::Misaligned { addr: 0x1004, len: 8 })
+//! );
+//! # Ok::<(), BreakpointError>(())
+//! ```
+//!
+//! The number of breakpoints that can be active at once is limited by the
+//! number of debug registers: four on x86.
+
+use crate::bindings::{self, perf_event_attr};
+use std::fmt;
+
+/// The kind of access a breakpoint traps.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum BreakpointType {
+    /// Instruction execution, `HW_BREAKPOINT_X`.
+    Execute,
+
+    /// Data reads, `HW_BREAKPOINT_R`. Many processors, including x86, can't
+    /// watch for reads alone; use `ReadWrite` instead.
+    Read,
+
+    /// Data writes, `HW_BREAKPOINT_W`.
+    Write,
+
+    /// Data reads or writes, `HW_BREAKPOINT_RW`.
+    ReadWrite,
+}
+
+impl BreakpointType {
+    /// Return the `HW_BREAKPOINT_*` value for `perf_event_attr::bp_type`.
+    pub fn raw(self) -> u32 {
+        match self {
+            BreakpointType::Execute => bindings::HW_BREAKPOINT_X,
+            BreakpointType::Read => bindings::HW_BREAKPOINT_R,
+            BreakpointType::Write => bindings::HW_BREAKPOINT_W,
+            BreakpointType::ReadWrite => bindings::HW_BREAKPOINT_RW,
+        }
+    }
+}
+
+/// A hardware breakpoint or watchpoint.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Breakpoint {
+    pub type_: BreakpointType,
+    pub addr: u64,
+    pub len: u64,
+}
+
+impl Breakpoint {
+    pub fn new(type_: BreakpointType, addr: u64, len: u64) -> Breakpoint {
+        Breakpoint { type_, addr, len }
+    }
+
+    /// Trap execution of the instruction at `addr`.
+    pub fn execute(addr: u64) -> Breakpoint {
+        Breakpoint::new(
+            BreakpointType::Execute,
+            addr,
+            std::mem::size_of::<libc::c_long>() as u64,
+        )
+    }
+
+    /// Trap reads of the `len` bytes at `addr`.
+    pub fn read(addr: u64, len: u64) -> Breakpoint {
+        Breakpoint::new(BreakpointType::Read, addr, len)
+    }
+
+    /// Trap writes to the `len` bytes at `addr`.
+    pub fn write(addr: u64, len: u64) -> Breakpoint {
+        Breakpoint::new(BreakpointType::Write, addr, len)
+    }
+
+    /// Trap reads or writes of the `len` bytes at `addr`.
+    pub fn read_write(addr: u64, len: u64) -> Breakpoint {
+        Breakpoint::new(BreakpointType::ReadWrite, addr, len)
+    }
+
+    /// Check this breakpoint against the kernel's rules for lengths and
+    /// alignment.
+    pub fn validate(&self) -> Result<(), BreakpointError> {
+        if self.type_ == BreakpointType::Execute {
+            if self.len != std::mem::size_of::<libc::c_long>() as u64 {
+                return Err(BreakpointError::ExecuteLength { len: self.len });
+            }
+            return Ok(());
+        }
+
+        if !matches!(self.len, 1 | 2 | 4 | 8) {
+            return Err(BreakpointError::DataLength { len: self.len });
+        }
+        if self.addr & (self.len - 1) != 0 {
+            return Err(BreakpointError::Misaligned {
+                addr: self.addr,
+                len: self.len,
+            });
+        }
+        Ok(())
+    }
+
+    /// Return a `PERF_TYPE_BREAKPOINT` `perf_event_attr` for this breakpoint,
+    /// or an error if it breaks the kernel's rules.
+    ///
+    /// The result has `size`, `type_`, `bp_type`, `bp_addr` and `bp_len`
+    /// set, and all other fields zero.
+    pub fn attr(&self) -> Result<perf_event_attr, BreakpointError> {
+        self.validate()?;
+        let mut attrs = perf_event_attr {
+            size: std::mem::size_of::<perf_event_attr>() as u32,
+            type_: bindings::perf_type_id_PERF_TYPE_BREAKPOINT,
+            bp_type: self.type_.raw(),
+            ..Default::default()
+        };
+        attrs.set_bp_addr(self.addr);
+        attrs.set_bp_len(self.len);
+        Ok(attrs)
+    }
+}
+
+/// A breakpoint the kernel would reject.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum BreakpointError {
+    /// An execute breakpoint's length wasn't `sizeof(long)`.
+    ExecuteLength { len: u64 },
+
+    /// A data watchpoint's length wasn't 1, 2, 4, or 8.
+    DataLength { len: u64 },
+
+    /// A data watchpoint's address wasn't aligned to its length.
+    Misaligned { addr: u64, len: u64 },
+}
+
+impl fmt::Display for BreakpointError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BreakpointError::ExecuteLength { len } => write!(
+                f,
+                "execute breakpoint length must be {}, not {}",
+                std::mem::size_of::<libc::c_long>(),
+                len
+            ),
+            BreakpointError::DataLength { len } => {
+                write!(f, "watchpoint length must be 1, 2, 4, or 8, not {}", len)
+            }
+            BreakpointError::Misaligned { addr, len } => write!(
+                f,
+                "watchpoint address {:#x} is not aligned to its length {}",
+                addr, len
+            ),
+        }
+    }
+}
+
+impl std::error::Error for BreakpointError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    #[test]
+    fn rules() {
+        let long = std::mem::size_of::<libc::c_long>() as u64;
+        assert_eq!(Breakpoint::execute(0x401001).validate(), Ok(()));
+        assert_eq!(
+            Breakpoint::new(BreakpointType::Execute, 0x401000, 1).validate(),
+            Err(BreakpointError::ExecuteLength { len: 1 })
+        );
+        assert_eq!(Breakpoint::execute(0).len, long);
+
+        for &len in &[1, 2, 4, 8] {
+            assert_eq!(Breakpoint::read_write(0x1000, len).validate(), Ok(()));
+        }
+        assert_eq!(
+            Breakpoint::write(0x1000, 3).validate(),
+            Err(BreakpointError::DataLength { len: 3 })
+        );
+        assert_eq!(
+            Breakpoint::read(0x1000, 16).validate(),
+            Err(BreakpointError::DataLength { len: 16 })
+        );
+        assert_eq!(
+            Breakpoint::write(0x1002, 4).validate(),
+            Err(BreakpointError::Misaligned {
+                addr: 0x1002,
+                len: 4
+            })
+        );
+
+        let attrs = Breakpoint::read_write(0x2000, 4).attr().unwrap();
+        assert_eq!(attrs.type_, bindings::perf_type_id_PERF_TYPE_BREAKPOINT);
+        assert_eq!(attrs.bp_type, bindings::HW_BREAKPOINT_RW);
+        assert_eq!(attrs.bp_addr(), 0x2000);
+        assert_eq!(attrs.bp_len(), 4);
+    }
+
+    #[test]
+    fn watch_writes() {
+        use crate::{ioctls, perf_event_open_fd, read};
+        use std::os::unix::io::AsFd;
+
+        static WATCHED: AtomicU64 = AtomicU64::new(0);
+
+        let mut attrs = Breakpoint::write(WATCHED.as_ptr() as u64, 8)
+            .attr()
+            .unwrap();
+        attrs.set_disabled_flag(true);
+        attrs.set_exclude_kernel_flag(true);
+        attrs.set_exclude_hv_flag(true);
+        let fd = match unsafe { perf_event_open_fd(&mut attrs, 0, -1, -1, 0) } {
+            Ok(fd) => fd,
+            // Virtual machines often don't provide debug registers.
+            Err(err) if crate::test_util::unavailable(err.raw_os_error()) => return,
+            Err(err) => panic!("{}", err),
+        };
+
+        ioctls::enable(fd.as_fd(), ioctls::Scope::Event).unwrap();
+        for i in 0..3 {
+            WATCHED.store(i, Ordering::SeqCst);
+        }
+        ioctls::disable(fd.as_fd(), ioctls::Scope::Event).unwrap();
+        let value = read::read_value(fd.as_fd(), 0, 1).unwrap();
+        assert_eq!(value.values()[0].value, 3);
+    }
+}
@@ -0,0 +1,301 @@
+//! Counting the tasks in a cgroup.
+//!
+//! Passing `PERF_FLAG_PID_CGROUP` to `perf_event_open` makes its `pid`
+//! argument a file descriptor for a cgroup directory, and the event counts
+//! only while a task in that cgroup, or one of its descendants, is running on
+//! the event's CPU. Such an event must name a CPU, so counting a whole cgroup
+//! means opening an event on every CPU and adding up their counts.
+//!
+//! A [`CgroupTarget`] holds the directory of a cgroup v2 cgroup, and opens
+//! events and event groups on it:
+//!
+//! ```no_run
+//! use perf_event_open_sys::bindings::*;
+//! use perf_event_open_sys::cgroup::CgroupTarget;
+//! use perf_event_open_sys::per_cpu::Cpus;
+//!
+//! let mut cycles = perf_event_attr::default();
+//! cycles.size = std::mem::size_of::<perf_event_attr>() as u32;
+//! cycles.type_ = perf_type_id_PERF_TYPE_HARDWARE;
+//! cycles.config = perf_hw_id_PERF_COUNT_HW_CPU_CYCLES as u64;
+//! let mut instructions = cycles;
+//! instructions.config = perf_hw_id_PERF_COUNT_HW_INSTRUCTIONS as u64;
+//!
+//! let target = CgroupTarget::open("system.slice/nginx.service")?;
+//! let (mut events, handles) =
+//!     unsafe { target.groups(Cpus::online(), &[cycles, instructions])? };
+//! events.enable()?;
+//! // ... wait ...
+//! events.disable()?;
+//!
+//! let counts = events.read()?;
+//! println!("IPC: {}", counts[&handles[1]] as f64 / counts[&handles[0]] as f64);
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+//!
+//! Cgroup paths are resolved under [`CGROUP2_ROOT`] by default. On systems
+//! that mount the v2 hierarchy elsewhere, alongside v1 controllers for
+//! example, use [`cgroup2_mount`] to find it, and [`CgroupTarget::open_in`].
+
+use crate::bindings::{self, perf_event_attr};
+use crate::error::PerfOpenError;
+use crate::group::{Group, MemberHandle};
+use crate::per_cpu::{Cpus, PerCpu};
+use crate::perf_event_open_fd;
+use libc::{c_int, c_ulong};
+use std::ffi::{CString, OsString};
+use std::fs;
+use std::io;
+use std::mem;
+use std::os::unix::ffi::{OsStrExt, OsStringExt};
+use std::os::unix::io::{AsFd, AsRawFd, BorrowedFd, FromRawFd, OwnedFd};
+use std::path::{Component, Path, PathBuf};
+
+/// The usual mount point of the cgroup v2 hierarchy.
+pub const CGROUP2_ROOT: &str = "/sys/fs/cgroup";
+
+/// The `statfs` magic number of a cgroup v2 file system.
+const CGROUP2_SUPER_MAGIC: u64 = 0x6367_7270;
+
+/// Return the mount point of the cgroup v2 hierarchy, from
+/// `/proc/self/mounts`.
+///
+/// Return an error of kind `NotFound` if it isn't mounted.
+pub fn cgroup2_mount() -> io::Result<PathBuf> {
+    parse_mounts(&fs::read_to_string("/proc/self/mounts")?)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no cgroup2 file system is mounted"))
+}
+
+fn parse_mounts(mounts: &str) -> Option<PathBuf> {
+    mounts.lines().find_map(|line| {
+        let mut fields = line.split_whitespace();
+        let mount_point = fields.nth(1)?;
+        if fields.next()? != "cgroup2" {
+            return None;
+        }
+        // Spaces and other special characters are written as octal escapes,
+        // which may spell out bytes that aren't UTF-8.
+        Some(PathBuf::from(OsString::from_vec(unescape_octal(
+            mount_point,
+        ))))
+    })
+}
+
+fn unescape_octal(text: &str) -> Vec<u8> {
+    let mut out = Vec::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(index) = rest.find('\\') {
+        out.extend_from_slice(&rest.as_bytes()[..index]);
+        let escape = rest.get(index + 1..index + 4);
+        match escape.and_then(|digits| u8::from_str_radix(digits, 8).ok()) {
+            Some(byte) => {
+                out.push(byte);
+                rest = &rest[index + 4..];
+            }
+            None => {
+                out.push(b'\\');
+                rest = &rest[index + 1..];
+            }
+        }
+    }
+    out.extend_from_slice(rest.as_bytes());
+    out
+}
+
+/// A cgroup v2 cgroup, as a target for perf events.
+#[derive(Debug)]
+pub struct CgroupTarget {
+    path: PathBuf,
+    dir: OwnedFd,
+}
+
+impl CgroupTarget {
+    /// Open the cgroup `cgroup` under [`CGROUP2_ROOT`].
+    ///
+    /// `cgroup` is a path relative to the root of the hierarchy, as shown in
+    /// `/proc/<pid>/cgroup`; a leading `/` is ignored.
+    pub fn open<P: AsRef<Path>>(cgroup: P) -> io::Result<CgroupTarget> {
+        CgroupTarget::open_in(Path::new(CGROUP2_ROOT), cgroup)
+    }
+
+    /// Open the cgroup `cgroup` in the cgroup v2 hierarchy mounted at
+    /// `mount`.
+    ///
+    /// Return an error of kind `InvalidInput` if `cgroup` contains a `..`
+    /// component, and of kind `InvalidData` if the directory isn't in a
+    /// cgroup v2 file system.
+    pub fn open_in<P: AsRef<Path>>(mount: &Path, cgroup: P) -> io::Result<CgroupTarget> {
+        let mut path = mount.to_owned();
+        for component in cgroup.as_ref().components() {
+            match component {
+                Component::Normal(name) => path.push(name),
+                Component::RootDir | Component::CurDir => {}
+                Component::ParentDir | Component::Prefix(_) => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        format!("cgroup path {:?} leaves the hierarchy", cgroup.as_ref()),
+                    ));
+                }
+            }
+        }
+
+        let c_path = CString::new(path.as_os_str().as_bytes())?;
+        let fd = unsafe {
+            libc::open(
+                c_path.as_ptr(),
+                libc::O_RDONLY | libc::O_DIRECTORY | libc::O_CLOEXEC,
+            )
+        };
+        if fd < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        let dir = unsafe { OwnedFd::from_raw_fd(fd) };
+
+        let mut stat: libc::statfs = unsafe { mem::zeroed() };
+        if unsafe { libc::fstatfs(dir.as_raw_fd(), &mut stat) } < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        if stat.f_type as u64 != CGROUP2_SUPER_MAGIC {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("{} is not in a cgroup v2 file system", path.display()),
+            ));
+        }
+
+        Ok(CgroupTarget { path, dir })
+    }
+
+    /// Return the path of the cgroup's directory.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Return the file descriptor of the cgroup's directory.
+    pub fn dir(&self) -> BorrowedFd<'_> {
+        self.dir.as_fd()
+    }
+
+    /// Open `attrs` to count the tasks in this cgroup while they run on
+    /// `cpu`, with `group_fd` as the group leader, or -1.
+    ///
+    /// This passes `PERF_FLAG_PID_CGROUP`, along with `flags`.
+    ///
+    /// # Safety
+    ///
+    /// The measurements and other behaviors requested by `attrs` must be safe.
+    pub unsafe fn open_event(
+        &self,
+        attrs: &mut perf_event_attr,
+        cpu: c_int,
+        group_fd: c_int,
+        flags: c_ulong,
+    ) -> Result<OwnedFd, PerfOpenError> {
+        perf_event_open_fd(
+            attrs,
+            self.dir.as_raw_fd(),
+            cpu,
+            group_fd,
+            flags | bindings::PERF_FLAG_PID_CGROUP as c_ulong,
+        )
+    }
+
+    /// Open a group of the events `attrs` for this cgroup on every CPU
+    /// chosen by `cpus`, with `attrs[0]` as the leader.
+    ///
+    /// Return the groups, and the handles of the members, in the same order
+    /// as `attrs`. [`PerCpu::read`] sums the members' counts over all CPUs.
+    ///
+    /// # Safety
+    ///
+    /// The measurements and other behaviors requested by `attrs` must be safe.
+    pub unsafe fn groups(
+        &self,
+        cpus: Cpus,
+        attrs: &[perf_event_attr],
+    ) -> io::Result<(PerCpu<Group>, Vec<MemberHandle>)> {
+        PerCpu::groups_for(cpus, attrs, Some(self.dir.try_clone()?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mounts() {
+        let mounts = "\
+sysfs /sys sysfs rw,nosuid,nodev,noexec,relatime 0 0
+cgroup /sys/fs/cgroup/cpu cgroup rw,relatime,cpu 0 0
+cgroup2 /sys/fs/cgroup/unified cgroup2 rw,relatime 0 0
+";
+        assert_eq!(
+            parse_mounts(mounts),
+            Some(PathBuf::from("/sys/fs/cgroup/unified"))
+        );
+        assert_eq!(
+            parse_mounts("none /mnt/my\\040cgroups cgroup2 rw 0 0\n"),
+            Some(PathBuf::from("/mnt/my cgroups"))
+        );
+        assert_eq!(parse_mounts("proc /proc proc rw 0 0\n"), None);
+        assert_eq!(unescape_octal("a\\134b\\0"), b"a\\b\\0");
+        // Escapes may spell out bytes that aren't UTF-8.
+        assert_eq!(
+            parse_mounts("none /mnt/caf\\351 cgroup2 rw 0 0\n"),
+            Some(PathBuf::from(OsString::from_vec(b"/mnt/caf\xe9".to_vec())))
+        );
+    }
+
+    #[test]
+    fn resolve() {
+        let tmp = std::env::temp_dir();
+        assert_eq!(
+            CgroupTarget::open_in(&tmp, "a/../../etc")
+                .unwrap_err()
+                .kind(),
+            io::ErrorKind::InvalidInput
+        );
+        // The temporary directory exists, but isn't a cgroup.
+        assert_eq!(
+            CgroupTarget::open_in(&tmp, "/").unwrap_err().kind(),
+            io::ErrorKind::InvalidData
+        );
+    }
+
+    #[test]
+    fn count_own_cgroup() {
+        use crate::bindings::*;
+
+        let mount = match cgroup2_mount() {
+            Ok(mount) => mount,
+            Err(_) => return,
+        };
+        // Our own cgroup, as listed on the v2 line of /proc/self/cgroup.
+        let own = fs::read_to_string("/proc/self/cgroup").unwrap();
+        let own = match own.lines().find_map(|line| line.strip_prefix("0::")) {
+            Some(own) => own.to_string(),
+            None => return,
+        };
+        let target = CgroupTarget::open_in(&mount, &own).unwrap();
+        assert!(target.path().starts_with(&mount));
+
+        let clock = perf_event_attr {
+            size: std::mem::size_of::<perf_event_attr>() as u32,
+            type_: perf_type_id_PERF_TYPE_SOFTWARE,
+            config: perf_sw_ids_PERF_COUNT_SW_TASK_CLOCK as u64,
+            ..Default::default()
+        };
+        let (mut events, handles) = match unsafe { target.groups(Cpus::online(), &[clock]) } {
+            Ok(events) => events,
+            // Cgroup events need privileges, and the perf_event controller.
+            Err(err) if crate::test_util::unavailable(err.raw_os_error()) => return,
+            Err(err) => panic!("{}", err),
+        };
+        events.enable().unwrap();
+        let start = std::time::Instant::now();
+        while start.elapsed() < std::time::Duration::from_millis(5) {
+            std::hint::black_box(start.elapsed());
+        }
+        events.disable().unwrap();
+        assert!(events.read().unwrap()[&handles[0]] > 0);
+    }
+}
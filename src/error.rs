@@ -0,0 +1,203 @@
+//! Decoded errors from the `perf_event_open` system call.
+//!
+//! The raw [`perf_event_open`] function reports failure with a bare negative
+//! return value, which leaves every caller to work out what the kernel was
+//! trying to say. The
+//! [`perf_event_open_fd`] function decodes the errors the man page documents
+//! into a [`PerfOpenError`] instead.
+//!
+//! [`perf_event_open`]: crate::perf_event_open
+//! [`perf_event_open_fd`]: crate::perf_event_open_fd
+
+use std::fmt;
+use std::io;
+
+/// The file from which the kernel's `perf_event_paranoid` setting is read.
+const PARANOID_PATH: &str = "/proc/sys/kernel/perf_event_paranoid";
+
+/// An error returned by [`perf_event_open_fd`].
+///
+/// Each variant corresponds to an `errno` value that the
+/// [`perf_event_open(2)`][man] man page documents. Anything else is reported as
+/// [`Other`].
+///
+/// [`perf_event_open_fd`]: crate::perf_event_open_fd
+/// [`Other`]: PerfOpenError::Other
+/// [man]: http://man7.org/linux/man-pages/man2/perf_event_open.2.html
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum PerfOpenError {
+    /// `E2BIG`: the `perf_event_attr` struct requested features the running
+    /// kernel doesn't support, or its `size` field was out of range.
+    ///
+    /// The kernel writes the size it expected back into the struct's `size`
+    /// field, which is reported here. See the crate documentation for why it
+    /// is generally not necessary to adjust `size` to match.
+    TooBig {
+        /// The size of `perf_event_attr` the running kernel supports.
+        expected_size: u32,
+    },
+
+    /// `EACCES` or `EPERM`: the caller lacks the privileges this event
+    /// requires.
+    ///
+    /// This is usually governed by the `kernel.perf_event_paranoid` sysctl,
+    /// whose value at the time of the error is included if it could be read.
+    PermissionDenied {
+        /// The raw `errno` value, either `EACCES` or `EPERM`.
+        errno: i32,
+
+        /// The contents of `/proc/sys/kernel/perf_event_paranoid`.
+        paranoid: Option<i32>,
+    },
+
+    /// `ENOENT`: the `type_` and `config` fields name an event this kernel or
+    /// processor doesn't support.
+    NotFound,
+
+    /// `EOPNOTSUPP`: the event requires a hardware feature that isn't
+    /// available, like sampling on a PMU without interrupt support.
+    NotSupported,
+
+    /// `EMFILE`: the process has run out of file descriptors.
+    TooManyFiles,
+
+    /// `EBUSY`: another process has exclusive access to the PMU.
+    Busy,
+
+    /// `EINVAL`: the `perf_event_attr` struct, or some other argument, is
+    /// invalid.
+    InvalidArgument,
+
+    /// `ENODEV`: the event requires a feature the CPU doesn't have.
+    NoDevice,
+
+    /// Any other error.
+    Other(io::Error),
+}
+
+impl PerfOpenError {
+    /// Decode the `errno` value left by a failed call to [`perf_event_open`].
+    ///
+    /// The `expected_size` argument is the value of the `perf_event_attr`
+    /// struct's `size` field after the call, used for `E2BIG` errors.
+    ///
+    /// [`perf_event_open`]: crate::perf_event_open
+    pub fn from_raw_os_error(errno: i32, expected_size: u32) -> PerfOpenError {
+        match errno {
+            libc::E2BIG => PerfOpenError::TooBig { expected_size },
+            libc::EACCES | libc::EPERM => PerfOpenError::PermissionDenied {
+                errno,
+                paranoid: read_paranoid(),
+            },
+            libc::ENOENT => PerfOpenError::NotFound,
+            libc::EOPNOTSUPP => PerfOpenError::NotSupported,
+            libc::EMFILE => PerfOpenError::TooManyFiles,
+            libc::EBUSY => PerfOpenError::Busy,
+            libc::EINVAL => PerfOpenError::InvalidArgument,
+            libc::ENODEV => PerfOpenError::NoDevice,
+            _ => PerfOpenError::Other(io::Error::from_raw_os_error(errno)),
+        }
+    }
+
+    /// Return the raw `errno` value this error was decoded from.
+    pub fn raw_os_error(&self) -> Option<i32> {
+        Some(match self {
+            PerfOpenError::TooBig { .. } => libc::E2BIG,
+            PerfOpenError::PermissionDenied { errno, .. } => *errno,
+            PerfOpenError::NotFound => libc::ENOENT,
+            PerfOpenError::NotSupported => libc::EOPNOTSUPP,
+            PerfOpenError::TooManyFiles => libc::EMFILE,
+            PerfOpenError::Busy => libc::EBUSY,
+            PerfOpenError::InvalidArgument => libc::EINVAL,
+            PerfOpenError::NoDevice => libc::ENODEV,
+            PerfOpenError::Other(err) => return err.raw_os_error(),
+        })
+    }
+}
+
+fn read_paranoid() -> Option<i32> {
+    std::fs::read_to_string(PARANOID_PATH)
+        .ok()?
+        .trim()
+        .parse()
+        .ok()
+}
+
+impl fmt::Display for PerfOpenError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PerfOpenError::TooBig { expected_size } => write!(
+                f,
+                "perf_event_attr requests features this kernel doesn't support \
+                 (kernel expects size {})",
+                expected_size
+            ),
+            PerfOpenError::PermissionDenied { paranoid, .. } => {
+                f.write_str("permission denied opening perf event")?;
+                match paranoid {
+                    Some(level) => write!(
+                        f,
+                        "; {} is {}, consider lowering it or running with CAP_PERFMON",
+                        PARANOID_PATH, level
+                    ),
+                    None => write!(f, "; check {} or run with CAP_PERFMON", PARANOID_PATH),
+                }
+            }
+            PerfOpenError::NotFound => f.write_str("perf event type or config not supported"),
+            PerfOpenError::NotSupported => {
+                f.write_str("perf event requires a hardware feature that isn't available")
+            }
+            PerfOpenError::TooManyFiles => f.write_str("too many open files"),
+            PerfOpenError::Busy => f.write_str("PMU is in exclusive use by another process"),
+            PerfOpenError::InvalidArgument => f.write_str("invalid perf_event_open argument"),
+            PerfOpenError::NoDevice => f.write_str("perf event not supported by this CPU"),
+            PerfOpenError::Other(err) => err.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for PerfOpenError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            PerfOpenError::Other(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<PerfOpenError> for io::Error {
+    fn from(err: PerfOpenError) -> io::Error {
+        match err {
+            PerfOpenError::Other(err) => err,
+            err => io::Error::from_raw_os_error(err.raw_os_error().unwrap()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode() {
+        assert!(matches!(
+            PerfOpenError::from_raw_os_error(libc::E2BIG, 112),
+            PerfOpenError::TooBig { expected_size: 112 }
+        ));
+        assert!(matches!(
+            PerfOpenError::from_raw_os_error(libc::EPERM, 0),
+            PerfOpenError::PermissionDenied {
+                errno: libc::EPERM,
+                ..
+            }
+        ));
+        assert!(matches!(
+            PerfOpenError::from_raw_os_error(libc::ENOENT, 0),
+            PerfOpenError::NotFound
+        ));
+        let other = PerfOpenError::from_raw_os_error(libc::EINTR, 0);
+        assert_eq!(other.raw_os_error(), Some(libc::EINTR));
+        assert_eq!(io::Error::from(other).raw_os_error(), Some(libc::EINTR));
+    }
+}
@@ -0,0 +1,556 @@
+//! Building filters for the `SET_FILTER` ioctl.
+//!
+//! The `SET_FILTER` ioctl takes a string whose meaning depends on the event:
+//!
+//! -   For a tracepoint event, it is an ftrace filter expression over the
+//!     tracepoint's fields, like `prev_pid == 1 && next_comm ~ "kworker*"`.
+//!     Only records that match are counted or sampled.
+//!
+//! -   For an event on a PMU that supports address filters, like `intel_pt`
+//!     or CoreSight, it is a list of address ranges to trace, or at which to
+//!     start or stop tracing, like `filter 0x1000/0x200@/bin/ls`.
+//!
+//! The kernel rejects a malformed filter with a bare `EINVAL`. This module
+//! builds both kinds from typed values, and checks them first: a [`Filter`]
+//! against the fields of the tracepoint's [`Format`], and a list of
+//! [`AddressFilter`]s against the PMU's `nr_addr_filters`.
+//!
+//! ```
+//! use perf_event_open_sys::filter::Filter;
+//!
+//! let filter = Filter::common_pid(1234)
+//!     .and(Filter::glob("prev_comm", "kworker*").not())
+//!     .or(Filter::ge("prev_prio", 100));
+//! assert_eq!(
+//!     filter.to_string(),
+//!     r#"((common_pid == 1234) && (!(prev_comm ~ "kworker*"))) || (prev_prio >= 100)"#
+//! );
+//! ```
+//!
+//! [`Format`]: crate::tracepoint::Format
+
+use crate::ioctls;
+use crate::pmu::Pmu;
+use crate::tracepoint::{Field, Format};
+use std::ffi::CString;
+use std::fmt;
+use std::io;
+use std::os::unix::io::BorrowedFd;
+use std::path::PathBuf;
+
+/// A comparison operator.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum CompareOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+
+    /// `&`: true if the field and the value have any bits in common.
+    BitAnd,
+
+    /// `~`: a glob match against a string field.
+    Glob,
+}
+
+impl CompareOp {
+    /// Return the operator as it appears in a filter string.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            CompareOp::Eq => "==",
+            CompareOp::Ne => "!=",
+            CompareOp::Lt => "<",
+            CompareOp::Le => "<=",
+            CompareOp::Gt => ">",
+            CompareOp::Ge => ">=",
+            CompareOp::BitAnd => "&",
+            CompareOp::Glob => "~",
+        }
+    }
+
+    /// Return true if this operator can be applied to string fields.
+    pub fn applies_to_strings(self) -> bool {
+        matches!(self, CompareOp::Eq | CompareOp::Ne | CompareOp::Glob)
+    }
+
+    /// Return true if this operator can be applied to numeric fields.
+    pub fn applies_to_numbers(self) -> bool {
+        self != CompareOp::Glob
+    }
+}
+
+/// The value a field is compared against.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Value {
+    Signed(i64),
+    Unsigned(u64),
+    Str(String),
+}
+
+impl From<i64> for Value {
+    fn from(value: i64) -> Value {
+        Value::Signed(value)
+    }
+}
+
+impl From<i32> for Value {
+    fn from(value: i32) -> Value {
+        Value::Signed(value.into())
+    }
+}
+
+impl From<u64> for Value {
+    fn from(value: u64) -> Value {
+        Value::Unsigned(value)
+    }
+}
+
+impl From<u32> for Value {
+    fn from(value: u32) -> Value {
+        Value::Unsigned(value.into())
+    }
+}
+
+impl From<&str> for Value {
+    fn from(value: &str) -> Value {
+        Value::Str(value.to_string())
+    }
+}
+
+impl From<String> for Value {
+    fn from(value: String) -> Value {
+        Value::Str(value)
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Value::Signed(value) => write!(f, "{}", value),
+            Value::Unsigned(value) => write!(f, "{}", value),
+            Value::Str(value) => write!(f, "\"{}\"", value),
+        }
+    }
+}
+
+/// A tracepoint filter expression.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Filter {
+    Compare {
+        field: String,
+        op: CompareOp,
+        value: Value,
+    },
+    And(Box<Filter>, Box<Filter>),
+    Or(Box<Filter>, Box<Filter>),
+    Not(Box<Filter>),
+}
+
+impl Filter {
+    pub fn compare<V: Into<Value>>(field: &str, op: CompareOp, value: V) -> Filter {
+        Filter::Compare {
+            field: field.to_string(),
+            op,
+            value: value.into(),
+        }
+    }
+
+    pub fn eq<V: Into<Value>>(field: &str, value: V) -> Filter {
+        Filter::compare(field, CompareOp::Eq, value)
+    }
+
+    pub fn ne<V: Into<Value>>(field: &str, value: V) -> Filter {
+        Filter::compare(field, CompareOp::Ne, value)
+    }
+
+    pub fn lt<V: Into<Value>>(field: &str, value: V) -> Filter {
+        Filter::compare(field, CompareOp::Lt, value)
+    }
+
+    pub fn le<V: Into<Value>>(field: &str, value: V) -> Filter {
+        Filter::compare(field, CompareOp::Le, value)
+    }
+
+    pub fn gt<V: Into<Value>>(field: &str, value: V) -> Filter {
+        Filter::compare(field, CompareOp::Gt, value)
+    }
+
+    pub fn ge<V: Into<Value>>(field: &str, value: V) -> Filter {
+        Filter::compare(field, CompareOp::Ge, value)
+    }
+
+    /// Match records where `field` has any of the bits in `mask` set.
+    pub fn bit_and(field: &str, mask: u64) -> Filter {
+        Filter::compare(field, CompareOp::BitAnd, mask)
+    }
+
+    /// Match records where the string `field` matches the glob `pattern`,
+    /// which may use `*`, `?` and `[...]`.
+    pub fn glob(field: &str, pattern: &str) -> Filter {
+        Filter::compare(field, CompareOp::Glob, pattern)
+    }
+
+    /// Match records generated by the task `pid`.
+    pub fn common_pid(pid: i32) -> Filter {
+        Filter::eq("common_pid", pid)
+    }
+
+    pub fn and(self, other: Filter) -> Filter {
+        Filter::And(Box::new(self), Box::new(other))
+    }
+
+    pub fn or(self, other: Filter) -> Filter {
+        Filter::Or(Box::new(self), Box::new(other))
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn not(self) -> Filter {
+        Filter::Not(Box::new(self))
+    }
+
+    /// Check that every field this filter mentions exists in `format`, and
+    /// that its operators and values suit the field's type.
+    pub fn validate(&self, format: &Format) -> Result<(), FilterError> {
+        match self {
+            Filter::Compare { field, op, value } => {
+                let desc = format
+                    .field(field)
+                    .ok_or_else(|| FilterError::UnknownField(field.clone()))?;
+                validate_compare(desc, *op, value)
+            }
+            Filter::And(a, b) | Filter::Or(a, b) => {
+                a.validate(format)?;
+                b.validate(format)
+            }
+            Filter::Not(a) => a.validate(format),
+        }
+    }
+
+    /// Validate this filter against `format`, and install it on the
+    /// tracepoint event `fd`.
+    pub fn apply(&self, fd: BorrowedFd<'_>, format: &Format) -> Result<(), FilterError> {
+        self.validate(format)?;
+        let filter =
+            CString::new(self.to_string()).map_err(|_| FilterError::BadString(self.to_string()))?;
+        ioctls::set_filter(fd, &filter).map_err(FilterError::Io)
+    }
+}
+
+fn validate_compare(field: &Field, op: CompareOp, value: &Value) -> Result<(), FilterError> {
+    let mismatch = || FilterError::Mismatch {
+        field: field.name.clone(),
+        op,
+        value: value.clone(),
+    };
+    match value {
+        Value::Str(text) => {
+            if !field.is_string() || !op.applies_to_strings() {
+                return Err(mismatch());
+            }
+            // The kernel's filter parser has no escapes for quotes.
+            if text.contains('"') || text.contains('\0') {
+                return Err(FilterError::BadString(text.clone()));
+            }
+        }
+        Value::Signed(_) | Value::Unsigned(_) => {
+            if field.is_string() || !op.applies_to_numbers() {
+                return Err(mismatch());
+            }
+        }
+    }
+    Ok(())
+}
+
+impl fmt::Display for Filter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Filter::Compare { field, op, value } => {
+                write!(f, "{} {} {}", field, op.as_str(), value)
+            }
+            Filter::And(a, b) => write!(f, "({}) && ({})", a, b),
+            Filter::Or(a, b) => write!(f, "({}) || ({})", a, b),
+            Filter::Not(a) => write!(f, "!({})", a),
+        }
+    }
+}
+
+/// What an address filter does with its range.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum AddressAction {
+    /// Trace only within the range.
+    Filter,
+
+    /// Start tracing on reaching the range.
+    Start,
+
+    /// Stop tracing on reaching the range.
+    Stop,
+}
+
+/// An address range filter for PMUs like `intel_pt` and CoreSight.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct AddressFilter {
+    pub action: AddressAction,
+
+    /// The start of the range: a kernel address, or an offset into `object`.
+    pub start: u64,
+
+    /// The size of the range. `Filter` actions require a size.
+    pub size: Option<u64>,
+
+    /// The file the range lies in, or `None` for a kernel address.
+    pub object: Option<PathBuf>,
+}
+
+impl AddressFilter {
+    /// Trace only the `size` bytes at `start` in `object`, or in the kernel
+    /// if `object` is `None`.
+    pub fn filter(start: u64, size: u64, object: Option<PathBuf>) -> AddressFilter {
+        AddressFilter {
+            action: AddressAction::Filter,
+            start,
+            size: Some(size),
+            object,
+        }
+    }
+
+    fn validate(&self) -> Result<(), FilterError> {
+        if self.action == AddressAction::Filter && self.size.unwrap_or(0) == 0 {
+            return Err(FilterError::BadAddressFilter(self.to_string()));
+        }
+        if let Some(object) = &self.object {
+            let object = object.to_string_lossy();
+            if object.is_empty() || object.contains(|c: char| c.is_whitespace() || c == ',') {
+                return Err(FilterError::BadAddressFilter(self.to_string()));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for AddressFilter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let action = match self.action {
+            AddressAction::Filter => "filter",
+            AddressAction::Start => "start",
+            AddressAction::Stop => "stop",
+        };
+        write!(f, "{} {:#x}", action, self.start)?;
+        if let Some(size) = self.size {
+            write!(f, "/{:#x}", size)?;
+        }
+        if let Some(object) = &self.object {
+            write!(f, "@{}", object.display())?;
+        }
+        Ok(())
+    }
+}
+
+/// Render `filters` as a single `SET_FILTER` string, checking that `pmu`
+/// supports that many.
+pub fn address_filter_string(filters: &[AddressFilter], pmu: &Pmu) -> Result<String, FilterError> {
+    let limit = pmu.nr_addr_filters.unwrap_or(0);
+    if filters.len() > limit as usize {
+        return Err(FilterError::TooManyAddressFilters {
+            pmu: pmu.name.clone(),
+            count: filters.len(),
+            limit,
+        });
+    }
+    for filter in filters {
+        filter.validate()?;
+    }
+    Ok(filters
+        .iter()
+        .map(AddressFilter::to_string)
+        .collect::<Vec<_>>()
+        .join(","))
+}
+
+/// Install `filters` on the event `fd`, which was opened on `pmu`.
+pub fn set_address_filters(
+    fd: BorrowedFd<'_>,
+    filters: &[AddressFilter],
+    pmu: &Pmu,
+) -> Result<(), FilterError> {
+    let text = address_filter_string(filters, pmu)?;
+    let text = CString::new(text.as_str()).map_err(|_| FilterError::BadAddressFilter(text))?;
+    ioctls::set_filter(fd, &text).map_err(FilterError::Io)
+}
+
+/// An error building or installing a filter.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum FilterError {
+    /// The tracepoint has no field with the given name.
+    UnknownField(String),
+
+    /// The operator or value doesn't suit the field's type, like a glob
+    /// match on a numeric field.
+    Mismatch {
+        field: String,
+        op: CompareOp,
+        value: Value,
+    },
+
+    /// A string value can't be expressed in a filter.
+    BadString(String),
+
+    /// An address filter was malformed.
+    BadAddressFilter(String),
+
+    /// More address filters were given than the PMU supports.
+    TooManyAddressFilters {
+        pmu: String,
+        count: usize,
+        limit: u32,
+    },
+
+    /// The `SET_FILTER` ioctl failed.
+    Io(io::Error),
+}
+
+impl fmt::Display for FilterError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FilterError::UnknownField(field) => write!(f, "tracepoint has no field {:?}", field),
+            FilterError::Mismatch { field, op, value } => write!(
+                f,
+                "can't compare field {:?} with {} {}",
+                field,
+                op.as_str(),
+                value
+            ),
+            FilterError::BadString(text) => {
+                write!(f, "string can't appear in a filter: {:?}", text)
+            }
+            FilterError::BadAddressFilter(text) => {
+                write!(f, "malformed address filter: {:?}", text)
+            }
+            FilterError::TooManyAddressFilters { pmu, count, limit } => write!(
+                f,
+                "{} address filters given, but PMU {:?} supports only {}",
+                count, pmu, limit
+            ),
+            FilterError::Io(err) => err.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for FilterError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            FilterError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+    use std::path::Path;
+
+    const FORMAT: &str = "\
+name: sched_switch
+ID: 316
+format:
+\tfield:unsigned short common_type;\toffset:0;\tsize:2;\tsigned:0;
+\tfield:int common_pid;\toffset:4;\tsize:4;\tsigned:1;
+\tfield:char prev_comm[16];\toffset:8;\tsize:16;\tsigned:0;
+\tfield:pid_t prev_pid;\toffset:24;\tsize:4;\tsigned:1;
+\tfield:__data_loc char[] filename;\toffset:28;\tsize:4;\tsigned:0;
+";
+
+    #[test]
+    fn validate() {
+        let format = Format::parse(FORMAT).unwrap();
+
+        let good = Filter::common_pid(1)
+            .and(Filter::glob("filename", "/usr/*"))
+            .or(Filter::ne("prev_comm", "swapper/0").not())
+            .or(Filter::bit_and("common_type", 0x10));
+        assert!(good.validate(&format).is_ok());
+        assert_eq!(
+            good.to_string(),
+            r#"(((common_pid == 1) && (filename ~ "/usr/*")) || (!(prev_comm != "swapper/0"))) || (common_type & 16)"#
+        );
+
+        assert!(matches!(
+            Filter::eq("next_pid", 1).validate(&format),
+            Err(FilterError::UnknownField(name)) if name == "next_pid"
+        ));
+        assert!(matches!(
+            Filter::glob("prev_pid", "1*").validate(&format),
+            Err(FilterError::Mismatch { .. })
+        ));
+        assert!(matches!(
+            Filter::lt("prev_comm", "a").validate(&format),
+            Err(FilterError::Mismatch { .. })
+        ));
+        assert!(matches!(
+            Filter::eq("prev_comm", 3).validate(&format),
+            Err(FilterError::Mismatch { .. })
+        ));
+        assert!(matches!(
+            Filter::eq("prev_comm", "a\"b").validate(&format),
+            Err(FilterError::BadString(_))
+        ));
+    }
+
+    #[test]
+    fn address_filters() {
+        let mut pmu = Pmu {
+            name: "intel_pt".to_string(),
+            path: PathBuf::new(),
+            type_: 10,
+            cpumask: None,
+            cpus: None,
+            nr_addr_filters: Some(2),
+            mux_interval_ms: None,
+            caps: BTreeMap::new(),
+        };
+        let filters = [
+            AddressFilter::filter(0x1000, 0x200, Some("/bin/ls".into())),
+            AddressFilter {
+                action: AddressAction::Stop,
+                start: 0xffff_ffff_8100_0000,
+                size: None,
+                object: None,
+            },
+        ];
+        assert_eq!(
+            address_filter_string(&filters, &pmu).unwrap(),
+            "filter 0x1000/0x200@/bin/ls,stop 0xffffffff81000000"
+        );
+
+        pmu.nr_addr_filters = Some(1);
+        assert!(matches!(
+            address_filter_string(&filters, &pmu),
+            Err(FilterError::TooManyAddressFilters {
+                count: 2,
+                limit: 1,
+                ..
+            })
+        ));
+
+        pmu.nr_addr_filters = Some(2);
+        let bad = [AddressFilter::filter(0x1000, 0, None)];
+        assert!(matches!(
+            address_filter_string(&bad, &pmu),
+            Err(FilterError::BadAddressFilter(_))
+        ));
+        let bad = [AddressFilter::filter(
+            0x1000,
+            0x10,
+            Some(Path::new("/my file").into()),
+        )];
+        assert!(matches!(
+            address_filter_string(&bad, &pmu),
+            Err(FilterError::BadAddressFilter(_))
+        ));
+    }
+}
@@ -0,0 +1,346 @@
+//! Event groups, scheduled and read as a unit.
+//!
+//! Events opened with another event's file descriptor as `group_fd` form a
+//! group with that event as leader. The kernel schedules a group onto the PMU
+//! all at once or not at all, so the members' counts cover exactly the same
+//! time, and their ratios are meaningful. Reading the leader with
+//! `PERF_FORMAT_GROUP` returns every member's count in one atomic snapshot.
+//!
+//! A [`Group`] owns the leader and its followers. It opens the leader
+//! disabled, with `PERF_FORMAT_GROUP`, `PERF_FORMAT_ID` and both times in its
+//! `read_format`, and toggles the whole group with `PERF_IOC_FLAG_GROUP`:
+//!
+//! ```no_run
+//! use perf_event_open_sys::bindings::*;
+//! use perf_event_open_sys::group::Group;
+//!
+//! let mut cycles = perf_event_attr::default();
+//! cycles.size = std::mem::size_of::<perf_event_attr>() as u32;
+//! cycles.type_ = perf_type_id_PERF_TYPE_HARDWARE;
+//! cycles.config = perf_hw_id_PERF_COUNT_HW_CPU_CYCLES as u64;
+//! let mut instructions = cycles;
+//! instructions.config = perf_hw_id_PERF_COUNT_HW_INSTRUCTIONS as u64;
+//!
+//! let (mut group, cycles) = unsafe { Group::new(&mut cycles, 0, -1)? };
+//! let instructions = unsafe { group.add(&mut instructions, 0, -1)? };
+//!
+//! group.enable()?;
+//! // ... do work ...
+//! group.disable()?;
+//!
+//! let counts = group.read()?;
+//! println!("IPC: {}", counts[&instructions] as f64 / counts[&cycles] as f64);
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use crate::bindings::{self, perf_event_attr};
+use crate::error::PerfOpenError;
+use crate::ioctls::{self, Scope};
+use crate::perf_event_open_fd;
+use crate::read::{self, ReadValue};
+use libc::{c_int, c_ulong, pid_t};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::io;
+use std::os::unix::io::{AsFd, AsRawFd, BorrowedFd, OwnedFd};
+
+/// Identifies a member of a [`Group`]. The leader is a member too.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct MemberHandle(pub(crate) usize);
+
+/// An event group: a leader and the events that follow it.
+#[derive(Debug)]
+pub struct Group {
+    pid: pid_t,
+    cpu: c_int,
+    flags: c_ulong,
+    read_format: u64,
+
+    /// The members' file descriptors and ids, leader first. A
+    /// `MemberHandle` is an index into this vector.
+    members: Vec<(OwnedFd, u64)>,
+}
+
+impl Group {
+    /// Open a new group whose leader is `attrs`, measuring `pid` on `cpu`.
+    ///
+    /// This sets `disabled` on `attrs`, and adds `PERF_FORMAT_GROUP`,
+    /// `PERF_FORMAT_ID`, `PERF_FORMAT_TOTAL_TIME_ENABLED` and
+    /// `PERF_FORMAT_TOTAL_TIME_RUNNING` to its `read_format`. Return the group
+    /// and the leader's handle.
+    ///
+    /// # Safety
+    ///
+    /// The measurements and other behaviors requested by `attrs` must be safe.
+    pub unsafe fn new(
+        attrs: &mut perf_event_attr,
+        pid: pid_t,
+        cpu: c_int,
+    ) -> Result<(Group, MemberHandle), GroupError> {
+        Group::with_flags(attrs, pid, cpu, 0)
+    }
+
+    /// Like [`Group::new`], but pass `flags` to `perf_event_open` when opening
+    /// the leader and every member. For example, `PERF_FLAG_PID_CGROUP`
+    /// makes `pid` a cgroup directory's file descriptor.
+    ///
+    /// # Safety
+    ///
+    /// The measurements and other behaviors requested by `attrs` must be safe.
+    pub unsafe fn with_flags(
+        attrs: &mut perf_event_attr,
+        pid: pid_t,
+        cpu: c_int,
+        flags: c_ulong,
+    ) -> Result<(Group, MemberHandle), GroupError> {
+        attrs.set_disabled_flag(true);
+        attrs.read_format |= (bindings::perf_event_read_format_PERF_FORMAT_GROUP
+            | bindings::perf_event_read_format_PERF_FORMAT_ID
+            | bindings::perf_event_read_format_PERF_FORMAT_TOTAL_TIME_ENABLED
+            | bindings::perf_event_read_format_PERF_FORMAT_TOTAL_TIME_RUNNING)
+            as u64;
+        let fd = perf_event_open_fd(attrs, pid, cpu, -1, flags).map_err(GroupError::Open)?;
+        let id = ioctls::id(fd.as_fd()).map_err(GroupError::Io)?;
+        let group = Group {
+            pid,
+            cpu,
+            flags,
+            read_format: attrs.read_format,
+            members: vec![(fd, id)],
+        };
+        Ok((group, MemberHandle(0)))
+    }
+
+    /// Open `attrs` as a new member of this group, measuring `pid` on `cpu`,
+    /// which must be the same as the leader's.
+    ///
+    /// This clears `disabled` on `attrs`, so the member counts whenever the
+    /// leader does.
+    ///
+    /// # Safety
+    ///
+    /// The measurements and other behaviors requested by `attrs` must be safe.
+    pub unsafe fn add(
+        &mut self,
+        attrs: &mut perf_event_attr,
+        pid: pid_t,
+        cpu: c_int,
+    ) -> Result<MemberHandle, GroupError> {
+        if (pid, cpu) != (self.pid, self.cpu) {
+            return Err(GroupError::Mismatch {
+                leader: (self.pid, self.cpu),
+                member: (pid, cpu),
+            });
+        }
+        attrs.set_disabled_flag(false);
+        let fd = perf_event_open_fd(attrs, pid, cpu, self.leader().as_raw_fd(), self.flags)
+            .map_err(GroupError::Open)?;
+        let id = ioctls::id(fd.as_fd()).map_err(GroupError::Io)?;
+        self.members.push((fd, id));
+        Ok(MemberHandle(self.members.len() - 1))
+    }
+
+    /// Return the leader's file descriptor.
+    pub fn leader(&self) -> BorrowedFd<'_> {
+        self.members[0].0.as_fd()
+    }
+
+    /// Return the file descriptor of `member`.
+    pub fn fd(&self, member: MemberHandle) -> BorrowedFd<'_> {
+        self.members[member.0].0.as_fd()
+    }
+
+    /// Return the number of members, including the leader.
+    pub fn len(&self) -> usize {
+        self.members.len()
+    }
+
+    /// Return false: a group always has a leader.
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+
+    /// Enable every member of the group at once.
+    pub fn enable(&self) -> io::Result<()> {
+        ioctls::enable(self.leader(), Scope::Group)
+    }
+
+    /// Disable every member of the group at once.
+    pub fn disable(&self) -> io::Result<()> {
+        ioctls::disable(self.leader(), Scope::Group)
+    }
+
+    /// Reset every member's count to zero at once.
+    pub fn reset(&self) -> io::Result<()> {
+        ioctls::reset(self.leader(), Scope::Group)
+    }
+
+    /// Read every member's count in one snapshot, scaled for multiplexing.
+    ///
+    /// If the group has been enabled but the PMU has never been able to
+    /// schedule it, return [`GroupError::NotScheduled`], rather than zeros.
+    pub fn read(&self) -> Result<BTreeMap<MemberHandle, u64>, GroupError> {
+        let value = read::read_value(self.leader(), self.read_format, self.members.len())
+            .map_err(GroupError::Io)?;
+        scaled_counts(self.members.iter().map(|&(_, id)| id), &value)
+    }
+}
+
+/// Map each count in `value` to the handle of the member with that id,
+/// given the members' ids in order, and scale it for multiplexing.
+fn scaled_counts<I>(ids: I, value: &ReadValue) -> Result<BTreeMap<MemberHandle, u64>, GroupError>
+where
+    I: Iterator<Item = u64> + Clone,
+{
+    let mut counts = BTreeMap::new();
+    for counter in value.values() {
+        let handle = ids
+            .clone()
+            .position(|id| Some(id) == counter.id)
+            .map(MemberHandle)
+            .ok_or_else(|| {
+                GroupError::Io(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "group read returned an unknown event id",
+                ))
+            })?;
+        let scaled = value.scale(counter.value).ok_or(GroupError::NotScheduled)?;
+        counts.insert(handle, scaled);
+    }
+    Ok(counts)
+}
+
+/// An error building or reading a [`Group`].
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum GroupError {
+    /// Opening a member failed.
+    Open(PerfOpenError),
+
+    /// A member's pid and cpu differ from the leader's.
+    Mismatch {
+        leader: (pid_t, c_int),
+        member: (pid_t, c_int),
+    },
+
+    /// The group was enabled, but the PMU could never schedule all of its
+    /// members at once, so it never counted. Groups larger than the number
+    /// of hardware counters, or competing with pinned events, can't run.
+    NotScheduled,
+
+    /// An ioctl or read failed.
+    Io(io::Error),
+}
+
+impl fmt::Display for GroupError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GroupError::Open(err) => write!(f, "opening group member: {}", err),
+            GroupError::Mismatch { leader, member } => write!(
+                f,
+                "group member measures pid {} on cpu {}, but leader measures pid {} on cpu {}",
+                member.0, member.1, leader.0, leader.1
+            ),
+            GroupError::NotScheduled => f.write_str(
+                "PMU could not schedule the whole group; it has too many members, \
+                 or competes with pinned events",
+            ),
+            GroupError::Io(err) => err.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for GroupError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            GroupError::Open(err) => Some(err),
+            GroupError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<GroupError> for io::Error {
+    fn from(err: GroupError) -> io::Error {
+        match err {
+            GroupError::Open(err) => err.into(),
+            GroupError::Io(err) => err,
+            other => io::Error::other(other),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::read::CounterValue;
+    use bindings::*;
+
+    fn software(config: u32) -> perf_event_attr {
+        perf_event_attr {
+            size: std::mem::size_of::<perf_event_attr>() as u32,
+            type_: perf_type_id_PERF_TYPE_SOFTWARE,
+            config: config as u64,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn software_group() {
+        let mut clock = software(perf_sw_ids_PERF_COUNT_SW_TASK_CLOCK);
+        let mut faults = software(perf_sw_ids_PERF_COUNT_SW_PAGE_FAULTS);
+        let (mut group, clock) = match unsafe { Group::new(&mut clock, 0, -1) } {
+            Ok(group) => group,
+            // perf_event_paranoid may forbid even this.
+            Err(GroupError::Open(err)) if crate::test_util::unavailable(err.raw_os_error()) => {
+                return
+            }
+            Err(err) => panic!("{}", err),
+        };
+        let faults = unsafe { group.add(&mut faults, 0, -1) }.unwrap();
+        assert_eq!(group.len(), 2);
+
+        assert!(matches!(
+            unsafe { group.add(&mut software(perf_sw_ids_PERF_COUNT_SW_DUMMY), 0, 0) },
+            Err(GroupError::Mismatch {
+                leader: (0, -1),
+                member: (0, 0)
+            })
+        ));
+
+        group.enable().unwrap();
+        let pages: Vec<Vec<u8>> = (0..16).map(|_| vec![1_u8; 1 << 16]).collect();
+        std::hint::black_box(&pages);
+        group.disable().unwrap();
+
+        let counts = group.read().unwrap();
+        assert_eq!(counts.len(), 2);
+        assert!(counts[&clock] > 0);
+        assert!(counts.contains_key(&faults));
+
+        group.reset().unwrap();
+        assert_eq!(group.read().unwrap()[&clock], 0);
+    }
+
+    #[test]
+    fn never_scheduled() {
+        const LEADER_ID: u64 = 17;
+        let value = ReadValue::Group {
+            time_enabled: Some(1000),
+            time_running: Some(0),
+            values: vec![CounterValue {
+                value: 0,
+                id: Some(LEADER_ID),
+                lost: None,
+            }],
+        };
+        assert!(matches!(
+            scaled_counts([LEADER_ID].iter().copied(), &value),
+            Err(GroupError::NotScheduled)
+        ));
+        assert!(matches!(
+            scaled_counts([LEADER_ID + 1].iter().copied(), &value),
+            Err(GroupError::Io(_))
+        ));
+    }
+}
@@ -0,0 +1,242 @@
+//! Building and decoding `PERF_TYPE_HW_CACHE` event configurations.
+//!
+//! A hardware cache event is identified by three values: which cache, which
+//! kind of operation, and whether to count accesses or misses. The kernel
+//! expects them packed into `perf_event_attr::config` as:
+//!
+//! ```text
+//! config = cache_id | (op_id << 8) | (op_result_id << 16)
+//! ```
+//!
+//! where each value comes from the `perf_hw_cache_id`, `perf_hw_cache_op_id`
+//! and `perf_hw_cache_op_result_id` enums respectively. The [`HwCacheEvent`]
+//! type does this packing, and its reverse, with typed enums for each
+//! dimension, so the shifts cannot be swapped:
+//!
+//! ```
+//! use perf_event_open_sys::bindings;
+//! use perf_event_open_sys::hw_cache::{CacheId, CacheOp, CacheResult, HwCacheEvent};
+//!
+//! let event = HwCacheEvent::new(CacheId::L1D, CacheOp::Read, CacheResult::Miss);
+//! assert_eq!(event.config(), 0x10000);
+//!
+//! let mut attrs = bindings::perf_event_attr::default();
+//! event.configure(&mut attrs);
+//! assert_eq!(attrs.type_, bindings::perf_type_id_PERF_TYPE_HW_CACHE);
+//! assert_eq!(HwCacheEvent::from_config(attrs.config), Ok(event));
+//! ```
+//!
+//! Not every combination is supported by every processor; the kernel rejects
+//! unsupported ones with `ENOENT` or `EINVAL` when the event is opened.
+
+use crate::bindings::{self, perf_event_attr};
+use std::convert::TryFrom;
+use std::fmt;
+
+/// Define a typed enum mirroring one of the kernel's `perf_hw_cache_*`
+/// enums, with a `TryFrom<u32>` conversion that rejects the `_MAX` sentinel
+/// and anything beyond it.
+macro_rules! define_cache_enum {
+    (
+        $(#[$attr:meta])*
+        $name:ident, $what:literal {
+            $( $(#[$vattr:meta])* $variant:ident = $value:path, )*
+        }
+    ) => {
+        $(#[$attr])*
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+        pub enum $name {
+            $( $(#[$vattr])* $variant, )*
+        }
+
+        impl $name {
+            /// Return the kernel's numeric value for this variant.
+            pub fn raw(self) -> u32 {
+                match self {
+                    $( $name::$variant => $value, )*
+                }
+            }
+        }
+
+        impl TryFrom<u32> for $name {
+            type Error = HwCacheConfigError;
+
+            fn try_from(raw: u32) -> Result<Self, HwCacheConfigError> {
+                $(
+                    if raw == $value {
+                        return Ok($name::$variant);
+                    }
+                )*
+                Err(HwCacheConfigError::OutOfRange { field: $what, value: raw })
+            }
+        }
+    };
+}
+
+define_cache_enum! {
+    /// Which cache to count, from `perf_hw_cache_id`.
+    CacheId, "cache id" {
+        /// Level 1 data cache.
+        L1D = bindings::perf_hw_cache_id_PERF_COUNT_HW_CACHE_L1D,
+        /// Level 1 instruction cache.
+        L1I = bindings::perf_hw_cache_id_PERF_COUNT_HW_CACHE_L1I,
+        /// Last-level cache.
+        LL = bindings::perf_hw_cache_id_PERF_COUNT_HW_CACHE_LL,
+        /// Data translation lookaside buffer.
+        DTLB = bindings::perf_hw_cache_id_PERF_COUNT_HW_CACHE_DTLB,
+        /// Instruction translation lookaside buffer.
+        ITLB = bindings::perf_hw_cache_id_PERF_COUNT_HW_CACHE_ITLB,
+        /// Branch prediction unit.
+        BPU = bindings::perf_hw_cache_id_PERF_COUNT_HW_CACHE_BPU,
+        /// Local memory accesses.
+        NODE = bindings::perf_hw_cache_id_PERF_COUNT_HW_CACHE_NODE,
+    }
+}
+
+define_cache_enum! {
+    /// Which kind of operation to count, from `perf_hw_cache_op_id`.
+    CacheOp, "cache op id" {
+        Read = bindings::perf_hw_cache_op_id_PERF_COUNT_HW_CACHE_OP_READ,
+        Write = bindings::perf_hw_cache_op_id_PERF_COUNT_HW_CACHE_OP_WRITE,
+        Prefetch = bindings::perf_hw_cache_op_id_PERF_COUNT_HW_CACHE_OP_PREFETCH,
+    }
+}
+
+define_cache_enum! {
+    /// Whether to count all accesses or only misses, from
+    /// `perf_hw_cache_op_result_id`.
+    CacheResult, "cache op result id" {
+        Access = bindings::perf_hw_cache_op_result_id_PERF_COUNT_HW_CACHE_RESULT_ACCESS,
+        Miss = bindings::perf_hw_cache_op_result_id_PERF_COUNT_HW_CACHE_RESULT_MISS,
+    }
+}
+
+/// A `PERF_TYPE_HW_CACHE` event: a cache, an operation, and a result.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct HwCacheEvent {
+    pub cache: CacheId,
+    pub op: CacheOp,
+    pub result: CacheResult,
+}
+
+impl HwCacheEvent {
+    pub fn new(cache: CacheId, op: CacheOp, result: CacheResult) -> HwCacheEvent {
+        HwCacheEvent { cache, op, result }
+    }
+
+    /// Return the value for `perf_event_attr::config` that selects this
+    /// event.
+    pub fn config(&self) -> u64 {
+        self.cache.raw() as u64 | (self.op.raw() as u64) << 8 | (self.result.raw() as u64) << 16
+    }
+
+    /// Decode a `PERF_TYPE_HW_CACHE` `config` value.
+    ///
+    /// Return an error if any of the three fields is out of range, or if any
+    /// bits above the result field are set.
+    pub fn from_config(config: u64) -> Result<HwCacheEvent, HwCacheConfigError> {
+        if config >> 24 != 0 {
+            return Err(HwCacheConfigError::ExtraBits(config));
+        }
+        let byte = |shift: u32| (config >> shift) as u32 & 0xff;
+        Ok(HwCacheEvent {
+            cache: CacheId::try_from(byte(0))?,
+            op: CacheOp::try_from(byte(8))?,
+            result: CacheResult::try_from(byte(16))?,
+        })
+    }
+
+    /// Set `attrs.type_` to `PERF_TYPE_HW_CACHE` and `attrs.config` to select
+    /// this event.
+    pub fn configure(&self, attrs: &mut perf_event_attr) {
+        attrs.type_ = bindings::perf_type_id_PERF_TYPE_HW_CACHE;
+        attrs.config = self.config();
+    }
+}
+
+/// An error decoding a `PERF_TYPE_HW_CACHE` configuration.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum HwCacheConfigError {
+    /// One of the three fields held its enum's `_MAX` value or larger.
+    OutOfRange { field: &'static str, value: u32 },
+
+    /// Bits above the result field were set.
+    ExtraBits(u64),
+}
+
+impl fmt::Display for HwCacheConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            HwCacheConfigError::OutOfRange { field, value } => {
+                write!(
+                    f,
+                    "hardware cache event has out-of-range {}: {}",
+                    field, value
+                )
+            }
+            HwCacheConfigError::ExtraBits(config) => {
+                write!(
+                    f,
+                    "hardware cache event config has extra bits set: {:#x}",
+                    config
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for HwCacheConfigError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bindings::*;
+
+    #[test]
+    fn round_trip() {
+        let caches = [
+            CacheId::L1D,
+            CacheId::L1I,
+            CacheId::LL,
+            CacheId::DTLB,
+            CacheId::ITLB,
+            CacheId::BPU,
+            CacheId::NODE,
+        ];
+        for &cache in &caches {
+            for &op in &[CacheOp::Read, CacheOp::Write, CacheOp::Prefetch] {
+                for &result in &[CacheResult::Access, CacheResult::Miss] {
+                    let event = HwCacheEvent::new(cache, op, result);
+                    assert_eq!(HwCacheEvent::from_config(event.config()), Ok(event));
+                }
+            }
+        }
+
+        let event = HwCacheEvent::new(CacheId::DTLB, CacheOp::Write, CacheResult::Miss);
+        assert_eq!(event.config(), 0x01_01_03);
+    }
+
+    #[test]
+    fn rejects_max() {
+        assert_eq!(
+            HwCacheEvent::from_config(perf_hw_cache_id_PERF_COUNT_HW_CACHE_MAX as u64),
+            Err(HwCacheConfigError::OutOfRange {
+                field: "cache id",
+                value: perf_hw_cache_id_PERF_COUNT_HW_CACHE_MAX
+            })
+        );
+        assert!(HwCacheEvent::from_config(
+            (perf_hw_cache_op_id_PERF_COUNT_HW_CACHE_OP_MAX as u64) << 8
+        )
+        .is_err());
+        assert!(HwCacheEvent::from_config(
+            (perf_hw_cache_op_result_id_PERF_COUNT_HW_CACHE_RESULT_MAX as u64) << 16
+        )
+        .is_err());
+        assert_eq!(
+            HwCacheEvent::from_config(1 << 24),
+            Err(HwCacheConfigError::ExtraBits(1 << 24))
+        );
+    }
+}
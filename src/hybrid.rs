@@ -0,0 +1,189 @@
+//! Extended hardware event configurations for hybrid processors.
+//!
+//! On processors with more than one kind of core, like Intel's Alder Lake
+//! with its `cpu_core` and `cpu_atom` PMUs, each kind of core has its own
+//! PMU, with its own dynamic type id. A `PERF_TYPE_HARDWARE` or
+//! `PERF_TYPE_HW_CACHE` event can select one of them by placing that type id
+//! in the upper 32 bits of `config`:
+//!
+//! ```text
+//! config = (pmu_type << PERF_PMU_TYPE_SHIFT) | (event & PERF_HW_EVENT_MASK)
+//! ```
+//!
+//! If the upper bits are zero, the kernel opens the event on every core PMU,
+//! as it would on a non-hybrid system. The [`ExtendedConfig`] type does the
+//! encoding and decoding, and [`hybrid_pmus`] finds the type ids to use:
+//!
+//! ```no_run
+//! use perf_event_open_sys::bindings;
+//! use perf_event_open_sys::hybrid::{self, ExtendedConfig};
+//!
+//! for pmu in hybrid::hybrid_pmus()? {
+//!     let mut attrs = bindings::perf_event_attr::default();
+//!     attrs.type_ = bindings::perf_type_id_PERF_TYPE_HARDWARE;
+//!     attrs.config =
+//!         ExtendedConfig::hardware(pmu.type_, bindings::perf_hw_id_PERF_COUNT_HW_CPU_CYCLES)
+//!             .config();
+//!     // ... open one cycle counter per kind of core
+//! }
+//! # Ok::<(), std::io::Error>(())
+//! ```
+//!
+//! Extended configurations were added in Linux 5.13.
+
+use crate::bindings::{self, PERF_HW_EVENT_MASK, PERF_PMU_TYPE_SHIFT};
+use crate::hw_cache::{HwCacheConfigError, HwCacheEvent};
+use crate::pmu::{PmuRegistry, SYSFS_PMU_ROOT};
+use std::io;
+use std::path::Path;
+
+/// A `PERF_TYPE_HARDWARE` or `PERF_TYPE_HW_CACHE` `config` value, split into
+/// the PMU it selects and the event itself.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ExtendedConfig {
+    /// The type id of the PMU to count on, or `None` to count on all core
+    /// PMUs.
+    pub pmu_type: Option<u32>,
+
+    /// The event: a `perf_hw_id` value, or an encoded hardware cache event.
+    pub event: u32,
+}
+
+impl ExtendedConfig {
+    pub fn new(pmu_type: Option<u32>, event: u32) -> ExtendedConfig {
+        ExtendedConfig { pmu_type, event }
+    }
+
+    /// Select the generic hardware event `hw_id`, one of the
+    /// `perf_hw_id_PERF_COUNT_HW_*` constants, on the PMU `pmu_type`.
+    pub fn hardware(pmu_type: u32, hw_id: bindings::perf_hw_id) -> ExtendedConfig {
+        ExtendedConfig::new(Some(pmu_type), hw_id)
+    }
+
+    /// Select the hardware cache event `event` on the PMU `pmu_type`.
+    pub fn hw_cache(pmu_type: u32, event: HwCacheEvent) -> ExtendedConfig {
+        ExtendedConfig::new(Some(pmu_type), event.config() as u32)
+    }
+
+    /// Return the value for `perf_event_attr::config`.
+    pub fn config(&self) -> u64 {
+        let pmu_type = self.pmu_type.unwrap_or(0) as u64;
+        pmu_type << PERF_PMU_TYPE_SHIFT | (self.event & PERF_HW_EVENT_MASK) as u64
+    }
+
+    /// Split a `config` value into its PMU type and event.
+    ///
+    /// A PMU type of zero in the upper bits is reported as `None`.
+    pub fn from_config(config: u64) -> ExtendedConfig {
+        let pmu_type = (config >> PERF_PMU_TYPE_SHIFT) as u32;
+        ExtendedConfig {
+            pmu_type: if pmu_type == 0 { None } else { Some(pmu_type) },
+            event: (config & PERF_HW_EVENT_MASK as u64) as u32,
+        }
+    }
+
+    /// Decode `event` as a hardware cache event.
+    pub fn hw_cache_event(&self) -> Result<HwCacheEvent, HwCacheConfigError> {
+        HwCacheEvent::from_config(self.event as u64)
+    }
+}
+
+/// A core PMU on a hybrid system.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HybridPmu {
+    /// The PMU's name, like `cpu_core` or `cpu_atom`.
+    pub name: String,
+
+    /// The PMU's dynamic type id, for [`ExtendedConfig::pmu_type`].
+    pub type_: u32,
+
+    /// The CPUs this PMU covers, in the kernel's CPU list format (for
+    /// example, `"0-15"`).
+    pub cpus: String,
+}
+
+/// Return the core PMUs of a hybrid system, ordered by type id.
+///
+/// The kernel gives each hybrid core PMU a `cpus` file listing the CPUs it
+/// covers; on a non-hybrid system, there are none, and this returns an empty
+/// vector.
+pub fn hybrid_pmus() -> io::Result<Vec<HybridPmu>> {
+    hybrid_pmus_in(Path::new(SYSFS_PMU_ROOT))
+}
+
+/// Like [`hybrid_pmus`], but look for PMUs in `root` instead of
+/// [`SYSFS_PMU_ROOT`].
+///
+/// [`SYSFS_PMU_ROOT`]: crate::pmu::SYSFS_PMU_ROOT
+pub fn hybrid_pmus_in(root: &Path) -> io::Result<Vec<HybridPmu>> {
+    let mut pmus: Vec<HybridPmu> = PmuRegistry::load_from(root)?
+        .iter()
+        .filter_map(|pmu| {
+            Some(HybridPmu {
+                name: pmu.name.clone(),
+                type_: pmu.type_,
+                cpus: pmu.cpus.clone()?,
+            })
+        })
+        .collect();
+    pmus.sort_by_key(|pmu| pmu.type_);
+    Ok(pmus)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hw_cache::{CacheId, CacheOp, CacheResult};
+    use crate::test_util::TempTree;
+    use bindings::*;
+
+    #[test]
+    fn encode_decode() {
+        let cycles = ExtendedConfig::hardware(8, perf_hw_id_PERF_COUNT_HW_CPU_CYCLES);
+        assert_eq!(cycles.config(), 0x8_0000_0000);
+        assert_eq!(ExtendedConfig::from_config(cycles.config()), cycles);
+
+        let misses = HwCacheEvent::new(CacheId::LL, CacheOp::Read, CacheResult::Miss);
+        let config = ExtendedConfig::hw_cache(4, misses).config();
+        assert_eq!(config, 0x4_0001_0002);
+        assert_eq!(
+            ExtendedConfig::from_config(config).hw_cache_event(),
+            Ok(misses)
+        );
+
+        let plain = ExtendedConfig::from_config(perf_hw_id_PERF_COUNT_HW_INSTRUCTIONS as u64);
+        assert_eq!(plain.pmu_type, None);
+        assert_eq!(plain.event, perf_hw_id_PERF_COUNT_HW_INSTRUCTIONS);
+    }
+
+    #[test]
+    fn discover() {
+        let tree = TempTree::new("hybrid");
+        let pmu = |name: &str, type_: &str, cpus: Option<&str>| {
+            tree.write(format!("{}/type", name), type_);
+            if let Some(cpus) = cpus {
+                tree.write(format!("{}/cpus", name), cpus);
+            }
+        };
+        pmu("software", "1\n", None);
+        pmu("cpu_core", "8\n", Some("0-15\n"));
+        pmu("cpu_atom", "4\n", Some("16-23\n"));
+
+        let pmus = hybrid_pmus_in(tree.path()).unwrap();
+        assert_eq!(
+            pmus,
+            [
+                HybridPmu {
+                    name: "cpu_atom".to_string(),
+                    type_: 4,
+                    cpus: "16-23".to_string(),
+                },
+                HybridPmu {
+                    name: "cpu_core".to_string(),
+                    type_: 8,
+                    cpus: "0-15".to_string(),
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,398 @@
+//! Ioctls for use with `perf_event_open` file descriptors.
+//!
+//! See the [`perf_event_open(2)`][man] man page for details.
+//!
+//! This module provides two sets of functions:
+//!
+//! -   The functions with upper-case names, like [`ENABLE`] and [`ID`], are
+//!     direct, `unsafe` wrappers for the `ioctl` system call. On error, these
+//!     return `-1` and set the C `errno` value.
+//!
+//! -   The functions with lower-case names, like [`enable`] and [`id`], take
+//!     a [`BorrowedFd`], pass their arguments the way the kernel expects, and
+//!     return an [`io::Result`].
+//!
+//! For example:
+//!
+//! ```no_run
+//! use perf_event_open_sys::ioctls::{self, Scope};
+//! # fn example(fd: std::os::unix::io::BorrowedFd) -> std::io::Result<()> {
+//!
+//! ioctls::reset(fd, Scope::Group)?;
+//! ioctls::enable(fd, Scope::Group)?;
+//! // ... run the code being measured ...
+//! ioctls::disable(fd, Scope::Group)?;
+//! let id = ioctls::id(fd)?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [man]: http://man7.org/linux/man-pages/man2/perf_event_open.2.html
+use crate::bindings::{self, perf_event_attr, perf_event_query_bpf};
+use std::ffi::CStr;
+use std::io;
+use std::os::raw::{c_char, c_int, c_uint, c_ulong};
+use std::os::unix::io::{AsRawFd, BorrowedFd};
+
+macro_rules! define_ioctls {
+    ( $( $args:tt )* ) => {
+        $(
+            define_ioctl!($args);
+        )*
+    }
+}
+
+macro_rules! define_ioctl {
+    ({ $name:ident, $ioctl:ident, $arg_type:ty }) => {
+        #[allow(clippy::missing_safety_doc)]
+        pub unsafe fn $name(fd: c_int, arg: $arg_type) -> c_int {
+            untyped_ioctl(fd, bindings::$ioctl, arg)
+        }
+    };
+}
+
+define_ioctls! {
+    { ENABLE, perf_event_ioctls_ENABLE, c_uint }
+    { DISABLE, perf_event_ioctls_DISABLE, c_uint }
+    { REFRESH, perf_event_ioctls_REFRESH, c_int }
+    { RESET, perf_event_ioctls_RESET, c_uint }
+    { PERIOD, perf_event_ioctls_PERIOD, u64 }
+    { SET_OUTPUT, perf_event_ioctls_SET_OUTPUT, c_int }
+    { SET_FILTER, perf_event_ioctls_SET_FILTER, *mut c_char }
+    { ID, perf_event_ioctls_ID, *mut u64 }
+    { SET_BPF, perf_event_ioctls_SET_BPF, u32 }
+    { PAUSE_OUTPUT, perf_event_ioctls_PAUSE_OUTPUT, u32 }
+    { QUERY_BPF, perf_event_ioctls_QUERY_BPF, *mut perf_event_query_bpf }
+    { MODIFY_ATTRIBUTES, perf_event_ioctls_MODIFY_ATTRIBUTES, *mut perf_event_attr }
+}
+
+unsafe fn untyped_ioctl<A>(fd: c_int, ioctl: bindings::perf_event_ioctls, arg: A) -> c_int {
+    #[cfg(any(target_env = "musl", target_os = "android"))]
+    return libc::ioctl(fd, ioctl as c_int, arg);
+
+    #[cfg(not(any(target_env = "musl", target_os = "android")))]
+    libc::ioctl(fd, ioctl as c_ulong, arg)
+}
+
+/// Which events an ioctl applies to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Scope {
+    /// Only the event the file descriptor refers to.
+    Event,
+
+    /// The event and, if it is a group leader, all the other members of its
+    /// group. This passes `PERF_IOC_FLAG_GROUP`.
+    Group,
+}
+
+impl Scope {
+    fn flags(self) -> c_uint {
+        match self {
+            Scope::Event => 0,
+            Scope::Group => bindings::perf_event_ioc_flags_PERF_IOC_FLAG_GROUP,
+        }
+    }
+}
+
+fn check(result: c_int) -> io::Result<c_int> {
+    if result == -1 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(result)
+}
+
+/// Enable the perf event `fd`, or its whole group.
+pub fn enable(fd: BorrowedFd<'_>, scope: Scope) -> io::Result<()> {
+    check(unsafe { ENABLE(fd.as_raw_fd(), scope.flags()) }).map(drop)
+}
+
+/// Disable the perf event `fd`, or its whole group.
+pub fn disable(fd: BorrowedFd<'_>, scope: Scope) -> io::Result<()> {
+    check(unsafe { DISABLE(fd.as_raw_fd(), scope.flags()) }).map(drop)
+}
+
+/// Reset the count of the perf event `fd`, or of its whole group, to zero.
+pub fn reset(fd: BorrowedFd<'_>, scope: Scope) -> io::Result<()> {
+    check(unsafe { RESET(fd.as_raw_fd(), scope.flags()) }).map(drop)
+}
+
+/// Enable the sampling perf event `fd` for `overflows` more overflows, after
+/// which it is disabled again.
+pub fn refresh(fd: BorrowedFd<'_>, overflows: c_int) -> io::Result<()> {
+    check(unsafe { REFRESH(fd.as_raw_fd(), overflows) }).map(drop)
+}
+
+/// Set the sampling period (or frequency, if the event's `freq` flag is set)
+/// of the perf event `fd`.
+pub fn period(fd: BorrowedFd<'_>, period: u64) -> io::Result<()> {
+    // The kernel reads the new period through a pointer, which the raw
+    // `PERIOD` wrapper's `u64` argument can't express.
+    let period: *const u64 = &period;
+    check(unsafe { untyped_ioctl(fd.as_raw_fd(), bindings::perf_event_ioctls_PERIOD, period) })
+        .map(drop)
+}
+
+/// Direct the output of the perf event `fd` to the ring buffer of `target`,
+/// or, given `None`, stop sending it elsewhere.
+///
+/// Both events must be on the same CPU, or both must be monitoring the same
+/// task.
+pub fn set_output(fd: BorrowedFd<'_>, target: Option<BorrowedFd<'_>>) -> io::Result<()> {
+    // The kernel compares its `unsigned long` argument against -1, so the
+    // descriptor must be sign-extended to the full width of a `long`, not
+    // passed as a `c_int`.
+    let target = target.map_or(-1, |target| target.as_raw_fd()) as libc::c_long;
+    check(unsafe {
+        untyped_ioctl(
+            fd.as_raw_fd(),
+            bindings::perf_event_ioctls_SET_OUTPUT,
+            target,
+        )
+    })
+    .map(drop)
+}
+
+/// Set the filter of the tracepoint or address-filtering perf event `fd`.
+pub fn set_filter(fd: BorrowedFd<'_>, filter: &CStr) -> io::Result<()> {
+    // The kernel only reads the string, despite the `*mut`.
+    check(unsafe { SET_FILTER(fd.as_raw_fd(), filter.as_ptr() as *mut c_char) }).map(drop)
+}
+
+/// Return the id of the perf event `fd`, as it appears in samples and
+/// `read_format` values with `PERF_FORMAT_ID`.
+pub fn id(fd: BorrowedFd<'_>) -> io::Result<u64> {
+    let mut id = 0;
+    check(unsafe { ID(fd.as_raw_fd(), &mut id) })?;
+    Ok(id)
+}
+
+/// Attach the BPF program `prog_fd` to the tracepoint, kprobe or uprobe perf
+/// event `fd`.
+pub fn set_bpf(fd: BorrowedFd<'_>, prog_fd: BorrowedFd<'_>) -> io::Result<()> {
+    check(unsafe { SET_BPF(fd.as_raw_fd(), prog_fd.as_raw_fd() as u32) }).map(drop)
+}
+
+/// Pause or resume writing to the ring buffer of the perf event `fd`.
+///
+/// While output is paused, records are discarded, and counted in
+/// `PERF_RECORD_LOST` records.
+pub fn pause_output(fd: BorrowedFd<'_>, paused: bool) -> io::Result<()> {
+    check(unsafe { PAUSE_OUTPUT(fd.as_raw_fd(), paused as u32) }).map(drop)
+}
+
+/// Update the attributes of the perf event `fd` from `attrs`.
+///
+/// At present, the kernel only supports this for breakpoint events, and only
+/// for changes to `bp_addr`, `bp_len`, `bp_type` and the `disabled` flag.
+///
+/// # Safety
+///
+/// The measurements and other behaviors requested by `attrs` must be safe,
+/// as for [`perf_event_open`].
+///
+/// [`perf_event_open`]: crate::perf_event_open
+pub unsafe fn modify_attributes(fd: BorrowedFd<'_>, attrs: &mut perf_event_attr) -> io::Result<()> {
+    check(MODIFY_ATTRIBUTES(fd.as_raw_fd(), attrs)).map(drop)
+}
+
+/// Return the ids of the BPF programs attached to the tracepoint, kprobe or
+/// uprobe perf event `fd`.
+///
+/// This takes care of allocating a `perf_event_query_bpf` with room for the
+/// ids, and retrying with a larger buffer if more programs are attached than
+/// it has room for.
+pub fn query_bpf(fd: BorrowedFd<'_>) -> io::Result<Vec<u32>> {
+    query_bpf_with(|query| unsafe { QUERY_BPF(fd.as_raw_fd(), query) })
+}
+
+/// The implementation of `query_bpf`, with the ioctl call abstracted out for
+/// testing.
+fn query_bpf_with<F>(mut ioctl: F) -> io::Result<Vec<u32>>
+where
+    F: FnMut(*mut perf_event_query_bpf) -> c_int,
+{
+    use std::mem::{align_of, size_of};
+
+    // The struct is two `u32` fields followed by a flexible array of `u32`
+    // ids, so a `Vec<u32>` provides both the right size and alignment.
+    const HEADER: usize = size_of::<perf_event_query_bpf>() / size_of::<u32>();
+    const _: () = assert!(align_of::<perf_event_query_bpf>() <= align_of::<u32>());
+
+    let mut capacity = 8;
+    loop {
+        let mut buf = vec![0_u32; HEADER + capacity];
+        let query = buf.as_mut_ptr() as *mut perf_event_query_bpf;
+        unsafe {
+            (*query).ids_len = capacity as u32;
+        }
+
+        let result = ioctl(query);
+        let prog_cnt = unsafe { (*query).prog_cnt } as usize;
+        if result == -1 {
+            let err = io::Error::last_os_error();
+            // The kernel reports ENOSPC if there are more programs than
+            // `ids_len` allows, but still sets `prog_cnt`.
+            if err.raw_os_error() == Some(libc::ENOSPC) && prog_cnt > capacity {
+                capacity = prog_cnt;
+                continue;
+            }
+            return Err(err);
+        }
+
+        buf.truncate(HEADER + prog_cnt.min(capacity));
+        buf.drain(..HEADER);
+        return Ok(buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::mem::size_of;
+    use std::os::unix::io::AsFd;
+
+    #[test]
+    fn software_counter() {
+        let mut attrs = perf_event_attr {
+            size: size_of::<perf_event_attr>() as u32,
+            type_: bindings::perf_type_id_PERF_TYPE_SOFTWARE,
+            config: bindings::perf_sw_ids_PERF_COUNT_SW_DUMMY as u64,
+            ..Default::default()
+        };
+        attrs.set_disabled_flag(true);
+
+        let fd = match unsafe { crate::perf_event_open_fd(&mut attrs, 0, -1, -1, 0) } {
+            Ok(fd) => fd,
+            Err(err) if crate::test_util::unavailable(err.raw_os_error()) => return,
+            Err(err) => panic!("{}", err),
+        };
+        let fd = fd.as_fd();
+
+        enable(fd, Scope::Group).unwrap();
+        disable(fd, Scope::Event).unwrap();
+        reset(fd, Scope::Event).unwrap();
+        assert_ne!(id(fd).unwrap(), 0);
+        set_output(fd, None).unwrap();
+
+        let file = std::fs::File::open("/dev/null").unwrap();
+        let err = id(file.as_fd()).unwrap_err();
+        assert_eq!(err.raw_os_error(), Some(libc::ENOTTY));
+    }
+
+    #[test]
+    fn sampling_software_event() {
+        use crate::ring_buffer::RingBuffer;
+
+        let mut attrs = perf_event_attr {
+            size: size_of::<perf_event_attr>() as u32,
+            type_: bindings::perf_type_id_PERF_TYPE_SOFTWARE,
+            config: bindings::perf_sw_ids_PERF_COUNT_SW_TASK_CLOCK as u64,
+            sample_type: bindings::perf_event_sample_format_PERF_SAMPLE_IP,
+            ..Default::default()
+        };
+        attrs.set_sample_period(1_000_000);
+        attrs.set_disabled_flag(true);
+        attrs.set_exclude_kernel_flag(true);
+        attrs.set_exclude_hv_flag(true);
+
+        let fd = match unsafe { crate::perf_event_open_fd(&mut attrs, 0, -1, -1, 0) } {
+            Ok(fd) => fd,
+            Err(err) if crate::test_util::unavailable(err.raw_os_error()) => return,
+            Err(err) => panic!("{}", err),
+        };
+        let fd = fd.as_fd();
+
+        // There's no ring buffer to pause yet.
+        let err = pause_output(fd, true).unwrap_err();
+        assert_eq!(err.raw_os_error(), Some(libc::EINVAL));
+        let mut ring = RingBuffer::new(fd, 4).unwrap();
+
+        // The kernel reads the period through a pointer, and rejects zero.
+        period(fd, 100_000).unwrap();
+        let err = period(fd, 0).unwrap_err();
+        assert_eq!(err.raw_os_error(), Some(libc::EINVAL));
+
+        let spin = || {
+            let mut x = 0_u64;
+            for i in 0..10_000_000 {
+                x = x.wrapping_mul(31).wrapping_add(i);
+            }
+            std::hint::black_box(x);
+        };
+        let mut drain = || {
+            let (mut samples, mut lost) = (0, 0);
+            while let Some(record) = ring.next_record() {
+                match record.header().type_ {
+                    bindings::perf_event_type_PERF_RECORD_SAMPLE => samples += 1,
+                    bindings::perf_event_type_PERF_RECORD_LOST => lost += 1,
+                    _ => {}
+                }
+            }
+            (samples, lost)
+        };
+
+        enable(fd, Scope::Event).unwrap();
+        pause_output(fd, true).unwrap();
+        spin();
+        assert_eq!(drain(), (0, 0));
+
+        // The samples dropped while paused are reported by a lost record
+        // ahead of the first new sample.
+        pause_output(fd, false).unwrap();
+        spin();
+        disable(fd, Scope::Event).unwrap();
+        let (samples, lost) = drain();
+        assert!(samples > 0);
+        assert_eq!(lost, 1);
+    }
+
+    #[test]
+    fn tracepoint_filter() {
+        let attrs = crate::tracepoint::Tracefs::find()
+            .and_then(|tracefs| tracefs.attr("sched:sched_switch"));
+        let mut attrs = match attrs {
+            Ok(attrs) => attrs,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return,
+            Err(err) if crate::test_util::unavailable(err.raw_os_error()) => return,
+            Err(err) => panic!("{}", err),
+        };
+        attrs.set_disabled_flag(true);
+
+        let fd = match unsafe { crate::perf_event_open_fd(&mut attrs, 0, -1, -1, 0) } {
+            Ok(fd) => fd,
+            Err(err) if crate::test_util::unavailable(err.raw_os_error()) => return,
+            Err(err) => panic!("{}", err),
+        };
+        let fd = fd.as_fd();
+
+        let filter = CStr::from_bytes_with_nul(b"prev_pid == 1 && next_pid != 0\0").unwrap();
+        set_filter(fd, filter).unwrap();
+
+        // The kernel parses the filter against the tracepoint's format, so
+        // a field it doesn't have is rejected.
+        let bogus = CStr::from_bytes_with_nul(b"nonesuch == 1\0").unwrap();
+        let err = set_filter(fd, bogus).unwrap_err();
+        assert_eq!(err.raw_os_error(), Some(libc::EINVAL));
+    }
+
+    #[test]
+    fn query_bpf_grows() {
+        let attached: Vec<u32> = (100..120).collect();
+        let mut calls = 0;
+        let ids = query_bpf_with(|query| unsafe {
+            calls += 1;
+            let query = &mut *query;
+            query.prog_cnt = attached.len() as u32;
+            let len = (query.ids_len as usize).min(attached.len());
+            std::ptr::copy_nonoverlapping(attached.as_ptr(), query.ids.as_mut_ptr(), len);
+            if len < attached.len() {
+                *libc::__errno_location() = libc::ENOSPC;
+                return -1;
+            }
+            0
+        })
+        .unwrap();
+        assert_eq!(ids, attached);
+        assert_eq!(calls, 2);
+    }
+}
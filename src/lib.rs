@@ -13,8 +13,65 @@
 //! `<linux/hw_breakpoint.h>`, generated with `bindgen`, are available in the
 //! [`bindings`] module.
 //!
+//! The [`attr`] module adds typed accessors to `perf_event_attr` for its flag
+//! bits and anonymous unions, with names that don't depend on how `bindgen`
+//! numbers the unions.
+//!
+//! The [`breakpoint`] module builds hardware breakpoint and watchpoint
+//! events, checking their lengths and alignment before the kernel does.
+//!
+//! The [`hw_cache`] module builds and decodes `PERF_TYPE_HW_CACHE` event
+//! configurations from typed cache, operation and result values.
+//!
+//! The [`hybrid`] module encodes hardware event configurations that select a
+//! particular core PMU on hybrid processors, and finds those PMUs' type ids.
+//!
+//! The [`pmu`] module lists the PMUs the kernel publishes in sysfs, with
+//! their dynamically assigned type numbers and other properties, and the
+//! [`pmu_event`] module compiles `perf`-style event strings like
+//! `cpu/event=0x3c,umask=0x0/` using the PMUs' sysfs format descriptions.
+//!
+//! The [`probe`] module builds `perf_event_attr` values for kprobes and
+//! uprobes on the dynamic `kprobe` and `uprobe` PMUs, without going through
+//! tracefs.
+//!
+//! The [`sigtrap`] module installs a `SIGTRAP` handler that dispatches the
+//! synchronous signals requested by the `sigtrap` flag to callbacks keyed by
+//! the event's `sig_data`.
+//!
+//! The [`tracepoint`] module looks up tracepoint ids in tracefs, parses
+//! their record formats, and decodes the raw data of tracepoint samples. The
+//! [`filter`] module builds and checks tracepoint filter expressions and
+//! address range filters for the `SET_FILTER` ioctl.
+//!
+//! The [`regs`] module decodes the register masks used by `sample_regs_user`
+//! and `sample_regs_intr` into the register indices the kernel dumps.
+//!
+//! The [`ring_buffer`] module maps a perf event's ring buffer and hands out
+//! the records the kernel writes to it.
+//!
+//! The [`record`] module decodes those records into a [`Record`] enum with a
+//! variant for each `PERF_RECORD_*` type, and the [`sample`] module decodes
+//! the contents of samples.
+//!
+//! The [`read`] module decodes the counter values returned by reading a perf
+//! event file descriptor, for every combination of `read_format` flags. The
+//! [`self_counter`] module lets a thread read its own counters without a
+//! system call, using `rdpmc` and the event's metadata page.
+//!
 //! There are several ioctls for use with `perf_event_open` file descriptors;
-//! see the [`ioctls`] module for those.
+//! see the [`ioctls`] module for those. It provides both raw, `unsafe`
+//! wrappers and safe functions returning `io::Result`.
+//!
+//! The [`group`] module opens event groups that are enabled, disabled and
+//! read as a unit. The [`per_cpu`] module opens system-wide events on every
+//! online CPU, or on the CPUs a PMU's `cpumask` names, sums their counts, and
+//! follows CPUs as they come online. The [`cgroup`] module does the same for
+//! the tasks in a cgroup.
+//!
+//! The [`perf_event_open_fd`] function wraps the raw system call, returning an
+//! [`OwnedFd`] on success and a [`PerfOpenError`] that decodes the documented
+//! `errno` values on failure.
 //!
 //! For a safe and convenient interface to this functionality, see the
 //! [`perf_event`] crate.
@@ -73,6 +130,11 @@
 //! packaged by Fedora as `kernel-headers-5.17.6-300.fc36.x86_64`, which
 //! corresponds to `PERF_EVENT_ATTR_SIZE_VER7`.
 //!
+//! Bindings are generated separately for each supported architecture, from
+//! the same kernel version, since system call numbers and some type layouts
+//! differ between them. The [`bindings`] module lists the supported
+//! architectures.
+//!
 //! As explained above, bugs aside, it is not necessary to use the version of
 //! these structures that matches the kernel you want to run under, so it should
 //! always be acceptable to use the latest version of this crate, even if you
@@ -164,15 +226,62 @@
 //! crate, which provides a safe interface to a subset of `perf_event_open`'s
 //! functionality.
 //!
+//! [`attr`]: attr/index.html
 //! [`bindings`]: bindings/index.html
+//! [`breakpoint`]: breakpoint/index.html
+//! [`cgroup`]: cgroup/index.html
+//! [`filter`]: filter/index.html
+//! [`group`]: group/index.html
+//! [`hw_cache`]: hw_cache/index.html
+//! [`hybrid`]: hybrid/index.html
 //! [`ioctls`]: ioctls/index.html
+//! [`per_cpu`]: per_cpu/index.html
+//! [`pmu`]: pmu/index.html
+//! [`pmu_event`]: pmu_event/index.html
+//! [`probe`]: probe/index.html
+//! [`read`]: read/index.html
+//! [`record`]: record/index.html
+//! [`Record`]: record/enum.Record.html
+//! [`regs`]: regs/index.html
+//! [`ring_buffer`]: ring_buffer/index.html
+//! [`sample`]: sample/index.html
+//! [`self_counter`]: self_counter/index.html
+//! [`sigtrap`]: sigtrap/index.html
+//! [`tracepoint`]: tracepoint/index.html
 //! [man]: http://man7.org/linux/man-pages/man2/perf_event_open.2.html
 //! [`perf_event`]: https://crates.io/crates/perf_event
 
+pub mod attr;
 pub mod bindings;
+pub mod breakpoint;
+pub mod cgroup;
+pub mod error;
+pub mod filter;
+pub mod group;
+pub mod hw_cache;
+pub mod hybrid;
+#[allow(dead_code, non_snake_case)]
+pub mod ioctls;
+pub mod per_cpu;
+pub mod pmu;
+pub mod pmu_event;
+pub mod probe;
+pub mod read;
+pub mod record;
+pub mod regs;
+pub mod ring_buffer;
+pub mod sample;
+pub mod self_counter;
+pub mod sigtrap;
+#[cfg(test)]
+mod test_util;
+pub mod tracepoint;
+
+pub use error::PerfOpenError;
 
 use libc::pid_t;
 use std::os::raw::{c_int, c_ulong};
+use std::os::unix::io::{FromRawFd, OwnedFd};
 
 /// The `perf_event_open` system call.
 ///
@@ -209,59 +318,33 @@ pub unsafe fn perf_event_open(
     ) as c_int
 }
 
-#[allow(dead_code, non_snake_case)]
-pub mod ioctls {
-    //! Ioctls for use with `perf_event_open` file descriptors.
-    //!
-    //! See the [`perf_event_open(2)`][man] man page for details.
-    //!
-    //! On error, these return `-1` and set the C `errno` value.
-    //!
-    //! [man]: http://man7.org/linux/man-pages/man2/perf_event_open.2.html
-    use crate::bindings::{self, perf_event_attr, perf_event_query_bpf};
-    use std::os::raw::{c_char, c_int, c_uint, c_ulong};
-
-    macro_rules! define_ioctls {
-        ( $( $args:tt )* ) => {
-            $(
-                define_ioctl!($args);
-            )*
-        }
-    }
-
-    macro_rules! define_ioctl {
-        ({ $name:ident, $ioctl:ident, $arg_type:ty }) => {
-            #[allow(clippy::missing_safety_doc)]
-            pub unsafe fn $name(fd: c_int, arg: $arg_type) -> c_int {
-                untyped_ioctl(fd, bindings::$ioctl, arg)
-            }
-        };
-    }
-
-    define_ioctls! {
-        { ENABLE, perf_event_ioctls_ENABLE, c_uint }
-        { DISABLE, perf_event_ioctls_DISABLE, c_uint }
-        { REFRESH, perf_event_ioctls_REFRESH, c_int }
-        { RESET, perf_event_ioctls_RESET, c_uint }
-        { PERIOD, perf_event_ioctls_PERIOD, u64 }
-        { SET_OUTPUT, perf_event_ioctls_SET_OUTPUT, c_int }
-        { SET_FILTER, perf_event_ioctls_SET_FILTER, *mut c_char }
-        { ID, perf_event_ioctls_ID, *mut u64 }
-        { SET_BPF, perf_event_ioctls_SET_BPF, u32 }
-        { PAUSE_OUTPUT, perf_event_ioctls_PAUSE_OUTPUT, u32 }
-        { QUERY_BPF, perf_event_ioctls_QUERY_BPF, *mut perf_event_query_bpf }
-        { MODIFY_ATTRIBUTES, perf_event_ioctls_MODIFY_ATTRIBUTES, *mut perf_event_attr }
+/// The `perf_event_open` system call, returning an owned file descriptor.
+///
+/// This calls [`perf_event_open`] and decodes any error into a
+/// [`PerfOpenError`]. On success, the returned [`OwnedFd`] closes the event
+/// when dropped.
+///
+/// If the kernel reports `E2BIG`, the error carries the size it wrote back to
+/// `attrs.size`.
+///
+/// # Safety
+///
+/// The measurements and other behaviors requested by `attrs` must be safe.
+pub unsafe fn perf_event_open_fd(
+    attrs: &mut bindings::perf_event_attr,
+    pid: pid_t,
+    cpu: c_int,
+    group_fd: c_int,
+    flags: c_ulong,
+) -> Result<OwnedFd, PerfOpenError> {
+    let result = perf_event_open(attrs, pid, cpu, group_fd, flags);
+    if result < 0 {
+        let err = std::io::Error::last_os_error();
+        return Err(match err.raw_os_error() {
+            Some(errno) => PerfOpenError::from_raw_os_error(errno, attrs.size),
+            None => PerfOpenError::Other(err),
+        });
     }
 
-    unsafe fn untyped_ioctl<A>(
-        fd: c_int,
-        ioctl: bindings::perf_event_ioctls,
-        arg: A,
-    ) -> c_int {
-        #[cfg(any(target_env = "musl", target_os = "android"))]
-        return libc::ioctl(fd, ioctl as c_int, arg);
-
-        #[cfg(not(any(target_env = "musl", target_os = "android")))]
-        libc::ioctl(fd, ioctl as c_ulong, arg)
-    }
+    Ok(OwnedFd::from_raw_fd(result))
 }
@@ -0,0 +1,969 @@
+//! System-wide events, opened once per CPU.
+//!
+//! An event that measures every task, with a `pid` of -1, must name a
+//! specific CPU, so monitoring the whole system means opening one event on
+//! each online CPU, as listed in `/sys/devices/system/cpu/online`. Uncore and
+//! other system-wide PMUs publish a `cpumask` naming the one CPU per package
+//! or die on which their events should be opened; and on hybrid processors,
+//! each core PMU's `cpus` file lists the CPUs it covers.
+//!
+//! A [`Cpus`] value computes the set of CPUs to use from those files, and a
+//! [`PerCpu`] holds one event, or event group, for each of them, and sums
+//! their counts:
+//!
+//! ```no_run
+//! use perf_event_open_sys::bindings::*;
+//! use perf_event_open_sys::per_cpu::{Cpus, PerCpu};
+//!
+//! let mut cycles = perf_event_attr::default();
+//! cycles.size = std::mem::size_of::<perf_event_attr>() as u32;
+//! cycles.type_ = perf_type_id_PERF_TYPE_HARDWARE;
+//! cycles.config = perf_hw_id_PERF_COUNT_HW_CPU_CYCLES as u64;
+//!
+//! let (mut events, handles) = unsafe { PerCpu::groups(Cpus::online(), &[cycles])? };
+//! events.enable()?;
+//! // ... wait ...
+//! events.disable()?;
+//! println!("cycles on all CPUs: {}", events.read()?[&handles[0]]);
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+//!
+//! [`PerCpu::events`] opens independent events instead, one `PerCpu` for
+//! each, whose counts are read and scaled separately.
+//!
+//! CPUs can come and go while events are open. [`PerCpu::rescan`] rereads
+//! the CPU set, opening events on CPUs that have come online, and [`watch`]
+//! starts a thread that rescans whenever the kernel announces a CPU hotplug
+//! event over netlink, or periodically if it can't listen for them.
+
+use crate::bindings::{self, perf_event_attr};
+use crate::group::{Group, GroupError, MemberHandle};
+use crate::ioctls::{self, Scope};
+use crate::perf_event_open_fd;
+use crate::pmu::{self, Pmu};
+use crate::read;
+use libc::{c_int, c_ulong};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::mem;
+use std::os::unix::io::{AsFd, AsRawFd, FromRawFd, OwnedFd};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+/// The file in which the kernel lists the online CPUs.
+pub const SYSFS_CPU_ONLINE: &str = "/sys/devices/system/cpu/online";
+
+/// Parse a list of CPUs in the kernel's format, like `0-3,8,10-11`.
+///
+/// Return the CPUs in increasing order, without duplicates. An empty or
+/// all-whitespace string is an empty list, as the kernel writes for an empty
+/// mask. Return an error of kind `InvalidData` if `text` is malformed.
+pub fn parse_cpu_list(text: &str) -> io::Result<Vec<u32>> {
+    let invalid = || {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("malformed CPU list: {:?}", text),
+        )
+    };
+    let number = |text: &str| text.parse::<u32>().map_err(|_| invalid());
+
+    let mut cpus = Vec::new();
+    let text = text.trim();
+    if text.is_empty() {
+        return Ok(cpus);
+    }
+    for item in text.split(',') {
+        match item.split_once('-') {
+            Some((first, last)) => {
+                let (first, last) = (number(first)?, number(last)?);
+                if first > last {
+                    return Err(invalid());
+                }
+                cpus.extend(first..=last);
+            }
+            None => cpus.push(number(item)?),
+        }
+    }
+    cpus.sort_unstable();
+    cpus.dedup();
+    Ok(cpus)
+}
+
+/// Return the CPUs listed in [`SYSFS_CPU_ONLINE`].
+pub fn online_cpus() -> io::Result<Vec<u32>> {
+    parse_cpu_list(&fs::read_to_string(SYSFS_CPU_ONLINE)?)
+}
+
+/// A rule for choosing the CPUs on which to open system-wide events.
+///
+/// The rule is reevaluated by [`Cpus::list`] each time it's called, so it
+/// tracks CPUs going on and offline, and uncore PMUs changing their
+/// `cpumask` in response.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Cpus {
+    online: PathBuf,
+    pmu: Option<PathBuf>,
+}
+
+impl Cpus {
+    /// Choose every online CPU.
+    pub fn online() -> Cpus {
+        Cpus::online_in(Path::new(SYSFS_CPU_ONLINE))
+    }
+
+    /// Choose every CPU listed in `online`, a file with the same format as
+    /// [`SYSFS_CPU_ONLINE`].
+    pub fn online_in(online: &Path) -> Cpus {
+        Cpus {
+            online: online.to_owned(),
+            pmu: None,
+        }
+    }
+
+    /// Restrict the choice to the CPUs that events on `pmu` should be opened
+    /// on: those in its `cpumask`, if it has one, or else those in its
+    /// `cpus`, if it has one.
+    pub fn for_pmu(self, pmu: &Pmu) -> Cpus {
+        Cpus {
+            pmu: Some(pmu.path.clone()),
+            ..self
+        }
+    }
+
+    /// Return the chosen CPUs, in increasing order.
+    pub fn list(&self) -> io::Result<Vec<u32>> {
+        let online = parse_cpu_list(&fs::read_to_string(&self.online)?)?;
+        match self.restriction()? {
+            Some(mask) => Ok(online
+                .into_iter()
+                .filter(|cpu| mask.contains(cpu))
+                .collect()),
+            None => Ok(online),
+        }
+    }
+
+    /// Return true if events on these CPUs belong to a PMU with a `cpumask`.
+    /// The kernel migrates such events to another CPU when theirs goes
+    /// offline.
+    fn migrates(&self) -> bool {
+        match &self.pmu {
+            Some(path) => path.join("cpumask").exists(),
+            None => false,
+        }
+    }
+
+    fn restriction(&self) -> io::Result<Option<Vec<u32>>> {
+        let path = match &self.pmu {
+            Some(path) => path,
+            None => return Ok(None),
+        };
+        let list = match pmu::read_optional(&path.join("cpumask"))? {
+            Some(mask) => Some(mask),
+            None => pmu::read_optional(&path.join("cpus"))?,
+        };
+        list.map(|list| parse_cpu_list(&list)).transpose()
+    }
+}
+
+/// An event, or set of events, that [`PerCpu`] can open on each CPU.
+pub trait CpuEvent {
+    /// Enable counting.
+    fn enable(&self) -> io::Result<()>;
+
+    /// Disable counting.
+    fn disable(&self) -> io::Result<()>;
+
+    /// Reset the count to zero.
+    fn reset(&self) -> io::Result<()>;
+}
+
+impl CpuEvent for OwnedFd {
+    fn enable(&self) -> io::Result<()> {
+        ioctls::enable(self.as_fd(), Scope::Event)
+    }
+
+    fn disable(&self) -> io::Result<()> {
+        ioctls::disable(self.as_fd(), Scope::Event)
+    }
+
+    fn reset(&self) -> io::Result<()> {
+        ioctls::reset(self.as_fd(), Scope::Event)
+    }
+}
+
+impl CpuEvent for Group {
+    fn enable(&self) -> io::Result<()> {
+        Group::enable(self)
+    }
+
+    fn disable(&self) -> io::Result<()> {
+        Group::disable(self)
+    }
+
+    fn reset(&self) -> io::Result<()> {
+        Group::reset(self)
+    }
+}
+
+/// A function that opens an event on a given CPU.
+type Opener<T> = Box<dyn FnMut(u32) -> io::Result<T> + Send>;
+
+/// One event, or event group, on each of a set of CPUs.
+pub struct PerCpu<T> {
+    cpus: Cpus,
+    open: Opener<T>,
+    enabled: bool,
+
+    /// The events on the CPUs currently in the set, by CPU.
+    events: BTreeMap<u32, T>,
+
+    /// Events whose CPUs have left the set. Their counts still contribute to
+    /// the totals.
+    retired: Vec<(u32, T)>,
+}
+
+/// The changes made by [`PerCpu::rescan`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Rescan {
+    /// CPUs on which new events were opened.
+    pub opened: Vec<u32>,
+
+    /// CPUs whose events were retired.
+    pub retired: Vec<u32>,
+
+    /// Events the kernel moved from one CPU to another, as `(from, to)`
+    /// pairs.
+    pub migrated: Vec<(u32, u32)>,
+}
+
+impl<T: CpuEvent> PerCpu<T> {
+    /// Call `open` for each CPU chosen by `cpus`, and collect the results.
+    ///
+    /// The events should be opened disabled; [`PerCpu::enable`] enables
+    /// them all. `open` is kept, to open events on CPUs that come online
+    /// later.
+    pub fn new<F>(cpus: Cpus, open: F) -> io::Result<PerCpu<T>>
+    where
+        F: FnMut(u32) -> io::Result<T> + Send + 'static,
+    {
+        let mut per_cpu = PerCpu {
+            cpus,
+            open: Box::new(open),
+            enabled: false,
+            events: BTreeMap::new(),
+            retired: Vec::new(),
+        };
+        for cpu in per_cpu.cpus.list()? {
+            let event = (per_cpu.open)(cpu)?;
+            per_cpu.events.insert(cpu, event);
+        }
+        Ok(per_cpu)
+    }
+
+    /// Return the event on `cpu`, if there is one.
+    pub fn get(&self, cpu: u32) -> Option<&T> {
+        self.events.get(&cpu)
+    }
+
+    /// Return an iterator over the CPUs in the set and their events, in
+    /// order by CPU.
+    pub fn iter(&self) -> impl Iterator<Item = (u32, &T)> {
+        self.events.iter().map(|(&cpu, event)| (cpu, event))
+    }
+
+    /// Return an iterator over the events whose CPUs have left the set, with
+    /// the CPUs they were opened on.
+    pub fn retired(&self) -> impl Iterator<Item = (u32, &T)> {
+        self.retired.iter().map(|(cpu, event)| (*cpu, event))
+    }
+
+    /// Return the number of CPUs in the set.
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    /// Return true if there are no CPUs in the set.
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    /// Enable the events on every CPU, including those opened by later
+    /// rescans.
+    ///
+    /// If any event can't be enabled, disable the ones that were, and
+    /// return the error.
+    pub fn enable(&mut self) -> io::Result<()> {
+        self.set_enabled(true)
+    }
+
+    /// Disable the events on every CPU.
+    ///
+    /// If any event can't be disabled, re-enable the ones that were, and
+    /// return the error.
+    pub fn disable(&mut self) -> io::Result<()> {
+        self.set_enabled(false)
+    }
+
+    fn set_enabled(&mut self, enabled: bool) -> io::Result<()> {
+        let set = |event: &T, enabled: bool| {
+            if enabled {
+                event.enable()
+            } else {
+                event.disable()
+            }
+        };
+        for (done, event) in self.events.values().enumerate() {
+            if let Err(err) = set(event, enabled) {
+                for event in self.events.values().take(done) {
+                    let _ = set(event, !enabled);
+                }
+                return Err(err);
+            }
+        }
+        self.enabled = enabled;
+        Ok(())
+    }
+
+    /// Reset every event's count to zero, and drop retired events.
+    pub fn reset(&mut self) -> io::Result<()> {
+        self.retired.clear();
+        self.events.values().try_for_each(T::reset)
+    }
+
+    /// Reread the set of CPUs, and bring the events up to date.
+    ///
+    /// Events are opened on CPUs that have joined the set, and enabled if
+    /// the set is enabled. The events of CPUs that have left the set are
+    /// retired: they no longer count, but what they counted is still
+    /// included in [`PerCpu::sum`].
+    ///
+    /// If the CPUs were chosen by a PMU's `cpumask`, the kernel moves the
+    /// events of a CPU that goes offline to another CPU in the same package,
+    /// and updates the `cpumask` to match. In this case, events that have
+    /// left the set are paired with CPUs that have joined it, in increasing
+    /// order, and treated as having moved there, rather than being retired
+    /// and replaced.
+    pub fn rescan(&mut self) -> io::Result<Rescan> {
+        let cpus = self.cpus.list()?;
+        let left: Vec<u32> = self
+            .events
+            .keys()
+            .copied()
+            .filter(|cpu| cpus.binary_search(cpu).is_err())
+            .collect();
+        let mut joined: Vec<u32> = cpus
+            .iter()
+            .copied()
+            .filter(|cpu| !self.events.contains_key(cpu))
+            .collect();
+
+        let mut rescan = Rescan::default();
+        let mut left = left.into_iter();
+        if self.cpus.migrates() {
+            let moves = joined.len().min(left.len());
+            for (from, to) in left.by_ref().take(moves).zip(joined.drain(..moves)) {
+                let event = self.events.remove(&from).unwrap();
+                self.events.insert(to, event);
+                rescan.migrated.push((from, to));
+            }
+        }
+        for cpu in left {
+            let event = self.events.remove(&cpu).unwrap();
+            self.retired.push((cpu, event));
+            rescan.retired.push(cpu);
+        }
+        for cpu in joined {
+            let event = (self.open)(cpu)?;
+            if self.enabled {
+                event.enable()?;
+            }
+            self.events.insert(cpu, event);
+            rescan.opened.push(cpu);
+        }
+        Ok(rescan)
+    }
+
+    /// Read every event, including retired ones, with `read`, and return
+    /// the sum of the results.
+    pub fn sum<C, E, F>(&self, mut read: F) -> Result<C, E>
+    where
+        C: Default + std::ops::AddAssign,
+        F: FnMut(&T) -> Result<C, E>,
+    {
+        let mut total = C::default();
+        for event in self
+            .events
+            .values()
+            .chain(self.retired.iter().map(|(_, event)| event))
+        {
+            total += read(event)?;
+        }
+        Ok(total)
+    }
+}
+
+impl PerCpu<Group> {
+    /// Open a group of the events `attrs` on every CPU chosen by `cpus`,
+    /// measuring all tasks, with `attrs[0]` as the leader.
+    ///
+    /// Return the groups, and the handles of the members, in the same order
+    /// as `attrs`. The handles are the same for every CPU's group.
+    ///
+    /// # Safety
+    ///
+    /// The measurements and other behaviors requested by `attrs` must be safe.
+    pub unsafe fn groups(
+        cpus: Cpus,
+        attrs: &[perf_event_attr],
+    ) -> io::Result<(PerCpu<Group>, Vec<MemberHandle>)> {
+        PerCpu::groups_for(cpus, attrs, None)
+    }
+
+    /// Like [`PerCpu::groups`], but if `cgroup` is given, measure only the
+    /// tasks in the cgroup whose directory it is.
+    pub(crate) unsafe fn groups_for(
+        cpus: Cpus,
+        attrs: &[perf_event_attr],
+        cgroup: Option<OwnedFd>,
+    ) -> io::Result<(PerCpu<Group>, Vec<MemberHandle>)> {
+        if attrs.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "an event group needs at least one event",
+            ));
+        }
+        let attrs = attrs.to_vec();
+        let handles = (0..attrs.len()).map(MemberHandle).collect();
+        let per_cpu = PerCpu::new(cpus, move |cpu| {
+            let (pid, flags) = match &cgroup {
+                Some(dir) => (dir.as_raw_fd(), bindings::PERF_FLAG_PID_CGROUP as c_ulong),
+                None => (-1, 0),
+            };
+            let cpu = cpu as c_int;
+            let mut attrs = attrs.clone();
+            let (mut group, _) = Group::with_flags(&mut attrs[0], pid, cpu, flags)?;
+            for member in &mut attrs[1..] {
+                group.add(member, pid, cpu)?;
+            }
+            Ok(group)
+        })?;
+        Ok((per_cpu, handles))
+    }
+
+    /// Read every CPU's group, and return each member's count, scaled for
+    /// multiplexing, summed over all CPUs.
+    pub fn read(&self) -> Result<BTreeMap<MemberHandle, u64>, GroupError> {
+        self.sum(|group| group.read().map(Counts))
+            .map(|counts| counts.0)
+    }
+}
+
+/// Per-member counts that add member by member.
+#[derive(Default)]
+struct Counts(BTreeMap<MemberHandle, u64>);
+
+impl std::ops::AddAssign for Counts {
+    fn add_assign(&mut self, other: Counts) {
+        for (member, count) in other.0 {
+            *self.0.entry(member).or_insert(0) += count;
+        }
+    }
+}
+
+/// The `read_format` of events opened by [`PerCpu::events`].
+const EVENT_READ_FORMAT: u64 = (bindings::perf_event_read_format_PERF_FORMAT_TOTAL_TIME_ENABLED
+    | bindings::perf_event_read_format_PERF_FORMAT_TOTAL_TIME_RUNNING)
+    as u64;
+
+impl PerCpu<OwnedFd> {
+    /// Open each of the events `attrs` on every CPU chosen by `cpus`,
+    /// measuring all tasks, as independent events rather than a group.
+    ///
+    /// Return one `PerCpu` for each event, in the same order as `attrs`.
+    /// This sets `disabled` on the events, and sets their `read_format` to
+    /// `PERF_FORMAT_TOTAL_TIME_ENABLED | PERF_FORMAT_TOTAL_TIME_RUNNING`, as
+    /// [`PerCpu::read`] expects.
+    ///
+    /// # Safety
+    ///
+    /// The measurements and other behaviors requested by `attrs` must be safe.
+    pub unsafe fn events(
+        cpus: Cpus,
+        attrs: &[perf_event_attr],
+    ) -> io::Result<Vec<PerCpu<OwnedFd>>> {
+        attrs
+            .iter()
+            .map(|&attr| {
+                let mut attr = attr;
+                attr.set_disabled_flag(true);
+                attr.read_format = EVENT_READ_FORMAT;
+                PerCpu::new(cpus.clone(), move |cpu| {
+                    let mut attr = attr;
+                    Ok(perf_event_open_fd(&mut attr, -1, cpu as c_int, -1, 0)?)
+                })
+            })
+            .collect()
+    }
+
+    /// Read every CPU's event, and return its count, scaled for
+    /// multiplexing, summed over all CPUs.
+    ///
+    /// The events must have been opened as [`PerCpu::events`] does, with
+    /// exactly both times in their `read_format`. If an event has been
+    /// enabled but the PMU has never been able to schedule it, return an
+    /// error, rather than leaving its CPU out of the total.
+    pub fn read(&self) -> io::Result<u64> {
+        self.sum(|fd| {
+            let value = read::read_value(fd.as_fd(), EVENT_READ_FORMAT, 1)?;
+            value
+                .scale(value.values()[0].value)
+                .ok_or_else(|| io::Error::other("event was enabled but never scheduled"))
+        })
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for PerCpu<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("PerCpu")
+            .field("cpus", &self.cpus)
+            .field("enabled", &self.enabled)
+            .field("events", &self.events)
+            .field("retired", &self.retired)
+            .finish()
+    }
+}
+
+/// A thread that keeps a [`PerCpu`] up to date as CPUs come and go.
+///
+/// Dropping a `Watcher` stops the thread.
+#[derive(Debug)]
+pub struct Watcher {
+    stop: Arc<AtomicBool>,
+
+    /// The most recent error the thread ran into, not yet taken.
+    error: Arc<Mutex<Option<io::Error>>>,
+
+    thread: Option<JoinHandle<()>>,
+}
+
+/// Start a thread that calls [`PerCpu::rescan`] on `events` whenever the
+/// kernel announces that a CPU has gone online or offline.
+///
+/// The thread listens for the kernel's uevents on a netlink socket. If it
+/// can't open one, it rescans every `interval` instead. Even with a socket,
+/// it rescans at least every `interval`, in case it missed an announcement.
+///
+/// Errors don't stop the thread: if the socket fails, it falls back to
+/// rescanning every `interval`, and a failed rescan is retried the next
+/// time. [`Watcher::take_error`] returns the most recent error.
+pub fn watch<T>(events: Arc<Mutex<PerCpu<T>>>, interval: Duration) -> Watcher
+where
+    T: CpuEvent + Send + 'static,
+{
+    let stop = Arc::new(AtomicBool::new(false));
+    let error = Arc::new(Mutex::new(None));
+    let thread = {
+        let stop = stop.clone();
+        let error = error.clone();
+        let report = move |err| *error.lock().unwrap_or_else(|err| err.into_inner()) = Some(err);
+        thread::spawn(move || {
+            let mut uevents = Uevents::open().ok();
+            let mut last = Instant::now();
+            while !stop.load(Ordering::Relaxed) {
+                let due = match uevents.as_ref().map(|uevents| uevents.wait(interval)) {
+                    Some(Ok(cpu)) => cpu || last.elapsed() >= interval,
+                    Some(Err(err)) => {
+                        report(err);
+                        uevents = None;
+                        true
+                    }
+                    None => {
+                        thread::sleep(interval);
+                        true
+                    }
+                };
+                if !due || stop.load(Ordering::Relaxed) {
+                    continue;
+                }
+                let mut events = events.lock().unwrap_or_else(|err| err.into_inner());
+                if let Err(err) = events.rescan() {
+                    report(err);
+                }
+                last = Instant::now();
+            }
+        })
+    };
+    Watcher {
+        stop,
+        error,
+        thread: Some(thread),
+    }
+}
+
+impl Watcher {
+    /// Return the most recent error the thread ran into, if any, and clear
+    /// it. The thread keeps running after errors.
+    pub fn take_error(&self) -> Option<io::Error> {
+        self.error
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .take()
+    }
+
+    /// Stop the thread, and return the most recent error it ran into that
+    /// hasn't been taken, if any.
+    pub fn stop(mut self) -> io::Result<()> {
+        self.join()
+    }
+
+    fn join(&mut self) -> io::Result<()> {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(thread) = self.thread.take() {
+            if thread.join().is_err() {
+                return Err(io::Error::other("CPU hotplug watcher panicked"));
+            }
+        }
+        match self.take_error() {
+            Some(err) => Err(err),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Drop for Watcher {
+    fn drop(&mut self) {
+        let _ = self.join();
+    }
+}
+
+/// A netlink socket receiving the kernel's uevents.
+struct Uevents {
+    fd: OwnedFd,
+}
+
+impl Uevents {
+    fn open() -> io::Result<Uevents> {
+        let fd = unsafe {
+            libc::socket(
+                libc::AF_NETLINK,
+                libc::SOCK_DGRAM | libc::SOCK_CLOEXEC,
+                libc::NETLINK_KOBJECT_UEVENT,
+            )
+        };
+        if fd < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        let fd = unsafe { OwnedFd::from_raw_fd(fd) };
+
+        let mut addr: libc::sockaddr_nl = unsafe { mem::zeroed() };
+        addr.nl_family = libc::AF_NETLINK as libc::sa_family_t;
+        addr.nl_groups = 1;
+        let bound = unsafe {
+            libc::bind(
+                fd.as_raw_fd(),
+                &addr as *const libc::sockaddr_nl as *const libc::sockaddr,
+                mem::size_of::<libc::sockaddr_nl>() as libc::socklen_t,
+            )
+        };
+        if bound < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(Uevents { fd })
+    }
+
+    /// Wait up to `timeout` for uevents, and drain any that are queued.
+    /// Return true if any of them were about a CPU.
+    fn wait(&self, timeout: Duration) -> io::Result<bool> {
+        let mut pollfd = libc::pollfd {
+            fd: self.fd.as_raw_fd(),
+            events: libc::POLLIN,
+            revents: 0,
+        };
+        let millis = timeout.as_millis().min(libc::c_int::MAX as u128) as libc::c_int;
+        if unsafe { libc::poll(&mut pollfd, 1, millis) } < 0 {
+            let err = io::Error::last_os_error();
+            if err.kind() != io::ErrorKind::Interrupted {
+                return Err(err);
+            }
+        }
+
+        let mut buf = [0_u8; 8192];
+        let mut cpu = false;
+        loop {
+            let len = unsafe {
+                libc::recv(
+                    self.fd.as_raw_fd(),
+                    buf.as_mut_ptr() as *mut libc::c_void,
+                    buf.len(),
+                    libc::MSG_DONTWAIT,
+                )
+            };
+            if len < 0 {
+                let err = io::Error::last_os_error();
+                return match err.raw_os_error() {
+                    Some(libc::EAGAIN) | Some(libc::EINTR) => Ok(cpu),
+                    // The socket overflowed, so we may have missed one.
+                    Some(libc::ENOBUFS) => Ok(true),
+                    _ => Err(err),
+                };
+            }
+            cpu |= cpu_uevent(&buf[..len as usize]).is_some();
+        }
+    }
+}
+
+/// If `message` is a uevent about a CPU, return its action and the CPU.
+fn cpu_uevent(message: &[u8]) -> Option<(&str, u32)> {
+    let header = message.split(|&byte| byte == 0).next()?;
+    let header = std::str::from_utf8(header).ok()?;
+    let (action, devpath) = header.split_once('@')?;
+    let cpu = devpath
+        .strip_prefix("/devices/system/cpu/cpu")?
+        .parse()
+        .ok()?;
+    Some((action, cpu))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempTree;
+    use std::cell::Cell;
+
+    #[test]
+    fn cpu_lists() {
+        assert_eq!(
+            parse_cpu_list("0-3,8,10-11\n").unwrap(),
+            [0, 1, 2, 3, 8, 10, 11]
+        );
+        assert_eq!(parse_cpu_list("5").unwrap(), [5]);
+        assert_eq!(parse_cpu_list("4-4,2,0-1,1").unwrap(), [0, 1, 2, 4]);
+        assert_eq!(parse_cpu_list("\n").unwrap(), Vec::<u32>::new());
+        for bad in ["3-1", "0,,1", "a", "0-", "-1", "1 ,2"] {
+            assert_eq!(
+                parse_cpu_list(bad).unwrap_err().kind(),
+                io::ErrorKind::InvalidData,
+                "{:?}",
+                bad
+            );
+        }
+        assert!(!online_cpus().unwrap().is_empty());
+    }
+
+    #[test]
+    fn uevents() {
+        assert_eq!(
+            cpu_uevent(b"online@/devices/system/cpu/cpu3\0ACTION=online\0SUBSYSTEM=cpu\0"),
+            Some(("online", 3))
+        );
+        assert_eq!(
+            cpu_uevent(b"offline@/devices/system/cpu/cpu12"),
+            Some(("offline", 12))
+        );
+        assert_eq!(cpu_uevent(b"add@/devices/system/cpu/cpufreq\0"), None);
+        assert_eq!(cpu_uevent(b"libudev\0\xfe\xed"), None);
+    }
+
+    /// A fake event that records its CPU and state. A broken one can't be
+    /// enabled or disabled.
+    #[derive(Debug)]
+    struct Fake {
+        cpu: u32,
+        enabled: Cell<bool>,
+        broken: bool,
+    }
+
+    impl CpuEvent for Fake {
+        fn enable(&self) -> io::Result<()> {
+            self.set(true)
+        }
+        fn disable(&self) -> io::Result<()> {
+            self.set(false)
+        }
+        fn reset(&self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl Fake {
+        fn set(&self, enabled: bool) -> io::Result<()> {
+            if self.broken {
+                return Err(io::Error::from_raw_os_error(libc::EIO));
+            }
+            self.enabled.set(enabled);
+            Ok(())
+        }
+    }
+
+    fn fake(cpu: u32) -> io::Result<Fake> {
+        Ok(Fake {
+            cpu,
+            enabled: Cell::new(false),
+            broken: false,
+        })
+    }
+
+    #[test]
+    fn core_hotplug() {
+        let tree = TempTree::new("per-cpu-core");
+        tree.write("online", "0-3\n");
+        tree.write("cpu_atom/type", "10\n");
+        tree.write("cpu_atom/cpus", "2-5\n");
+        let atom = Pmu::load(&tree.join("cpu_atom")).unwrap();
+        let cpus = Cpus::online_in(&tree.join("online")).for_pmu(&atom);
+        assert_eq!(cpus.list().unwrap(), [2, 3]);
+
+        let mut events = PerCpu::new(cpus, fake).unwrap();
+        assert_eq!(
+            events.iter().map(|(cpu, _)| cpu).collect::<Vec<_>>(),
+            [2, 3]
+        );
+        events.enable().unwrap();
+
+        tree.write("online", "0-2,4-5\n");
+        let rescan = events.rescan().unwrap();
+        assert_eq!(
+            rescan,
+            Rescan {
+                opened: vec![4, 5],
+                retired: vec![3],
+                migrated: vec![],
+            }
+        );
+        assert!(events.get(3).is_none());
+        assert!(events.get(5).unwrap().enabled.get());
+        assert_eq!(
+            events.retired().map(|(cpu, _)| cpu).collect::<Vec<_>>(),
+            [3]
+        );
+        assert_eq!(events.len(), 3);
+
+        // Retired events still count toward the total.
+        let total: Result<u32, io::Error> = events.sum(|event| Ok(event.cpu));
+        assert_eq!(total.unwrap(), 2 + 4 + 5 + 3);
+
+        assert_eq!(events.rescan().unwrap(), Rescan::default());
+        events.reset().unwrap();
+        assert_eq!(events.retired().count(), 0);
+    }
+
+    #[test]
+    fn enable_rollback() {
+        let tree = TempTree::new("per-cpu-rollback");
+        tree.write("online", "0-3\n");
+        let cpus = Cpus::online_in(&tree.join("online"));
+        let mut events = PerCpu::new(cpus, |cpu| {
+            Ok(Fake {
+                broken: cpu == 2,
+                ..fake(cpu)?
+            })
+        })
+        .unwrap();
+
+        // CPU 2's event fails, so the others are left disabled, and so are
+        // events opened by rescans.
+        assert_eq!(events.enable().unwrap_err().raw_os_error(), Some(libc::EIO));
+        assert!(events.iter().all(|(_, event)| !event.enabled.get()));
+        tree.write("online", "0-4\n");
+        assert_eq!(events.rescan().unwrap().opened, [4]);
+        assert!(!events.get(4).unwrap().enabled.get());
+    }
+
+    #[test]
+    fn uncore_migration() {
+        let tree = TempTree::new("per-cpu-uncore");
+        tree.write("online", "0-15\n");
+        tree.write("uncore_imc/type", "17\n");
+        tree.write("uncore_imc/cpumask", "0,8\n");
+        let imc = Pmu::load(&tree.join("uncore_imc")).unwrap();
+        let cpus = Cpus::online_in(&tree.join("online")).for_pmu(&imc);
+        let mut events = PerCpu::new(cpus, fake).unwrap();
+        assert_eq!(events.len(), 2);
+
+        // CPU 0 goes offline, and the kernel moves its events to CPU 1.
+        tree.write("online", "1-15\n");
+        tree.write("uncore_imc/cpumask", "1,8\n");
+        let rescan = events.rescan().unwrap();
+        assert_eq!(rescan.migrated, [(0, 1)]);
+        assert!(rescan.opened.is_empty() && rescan.retired.is_empty());
+        assert_eq!(events.get(1).unwrap().cpu, 0);
+
+        // The second package goes offline entirely.
+        tree.write("online", "1-7\n");
+        tree.write("uncore_imc/cpumask", "1\n");
+        assert_eq!(events.rescan().unwrap().retired, [8]);
+    }
+
+    #[test]
+    fn system_wide_groups() {
+        use crate::bindings::*;
+
+        let clock = perf_event_attr {
+            size: std::mem::size_of::<perf_event_attr>() as u32,
+            type_: perf_type_id_PERF_TYPE_SOFTWARE,
+            config: perf_sw_ids_PERF_COUNT_SW_CPU_CLOCK as u64,
+            ..Default::default()
+        };
+        let mut switches = clock;
+        switches.config = perf_sw_ids_PERF_COUNT_SW_CONTEXT_SWITCHES as u64;
+
+        let (mut events, handles) =
+            match unsafe { PerCpu::groups(Cpus::online(), &[clock, switches]) } {
+                Ok(events) => events,
+                // System-wide events need privileges.
+                Err(err) if crate::test_util::unavailable(err.raw_os_error()) => return,
+                Err(err) => panic!("{}", err),
+            };
+        assert_eq!(events.len(), online_cpus().unwrap().len());
+        events.enable().unwrap();
+        thread::sleep(Duration::from_millis(10));
+        events.disable().unwrap();
+
+        let counts = events.read().unwrap();
+        assert_eq!(counts.len(), 2);
+        assert!(counts[&handles[0]] > 0);
+
+        let events = Arc::new(Mutex::new(events));
+        let watcher = watch(events.clone(), Duration::from_millis(10));
+        thread::sleep(Duration::from_millis(30));
+        watcher.stop().unwrap();
+        assert!(!events.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn system_wide_events() {
+        use crate::bindings::*;
+
+        let clock = perf_event_attr {
+            size: std::mem::size_of::<perf_event_attr>() as u32,
+            type_: perf_type_id_PERF_TYPE_SOFTWARE,
+            config: perf_sw_ids_PERF_COUNT_SW_CPU_CLOCK as u64,
+            ..Default::default()
+        };
+        let mut switches = clock;
+        switches.config = perf_sw_ids_PERF_COUNT_SW_CONTEXT_SWITCHES as u64;
+
+        let mut events = match unsafe { PerCpu::events(Cpus::online(), &[clock, switches]) } {
+            Ok(events) => events,
+            // System-wide events need privileges.
+            Err(err) if crate::test_util::unavailable(err.raw_os_error()) => return,
+            Err(err) => panic!("{}", err),
+        };
+        assert_eq!(events.len(), 2);
+        for cpu_events in &mut events {
+            assert_eq!(cpu_events.len(), online_cpus().unwrap().len());
+            assert_eq!(cpu_events.read().unwrap(), 0);
+            cpu_events.enable().unwrap();
+        }
+        thread::sleep(Duration::from_millis(10));
+        for cpu_events in &mut events {
+            cpu_events.disable().unwrap();
+        }
+        assert!(events[0].read().unwrap() > 0);
+    }
+}
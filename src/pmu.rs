@@ -0,0 +1,254 @@
+//! Discovering dynamic PMUs from sysfs.
+//!
+//! The `perf_type_id` enum covers only the PMU types with fixed numbers,
+//! `PERF_TYPE_HARDWARE` through `PERF_TYPE_BREAKPOINT`. Every other PMU, like
+//! `kprobe`, `uprobe`, `intel_pt`, `msr`, `power` or an uncore unit, is
+//! assigned a type number when it registers, and the kernel publishes it in
+//! sysfs, along with some other properties:
+//!
+//! ```text
+//! /sys/bus/event_source/devices/<pmu>/type
+//! /sys/bus/event_source/devices/<pmu>/cpumask
+//! /sys/bus/event_source/devices/<pmu>/cpus
+//! /sys/bus/event_source/devices/<pmu>/nr_addr_filters
+//! /sys/bus/event_source/devices/<pmu>/perf_event_mux_interval_ms
+//! /sys/bus/event_source/devices/<pmu>/caps/*
+//! ```
+//!
+//! Only `type` is always present. A [`PmuRegistry`] reads all of these for
+//! every PMU, and can fill in a `perf_event_attr`'s `type_` field by name:
+//!
+//! ```no_run
+//! use perf_event_open_sys::bindings;
+//! use perf_event_open_sys::pmu::PmuRegistry;
+//!
+//! let pmus = PmuRegistry::load()?;
+//! let mut attrs = bindings::perf_event_attr::default();
+//! pmus.set_type(&mut attrs, "power")?;
+//! # Ok::<(), std::io::Error>(())
+//! ```
+
+use crate::bindings::perf_event_attr;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// The directory in which the kernel lists the PMUs it supports.
+pub const SYSFS_PMU_ROOT: &str = "/sys/bus/event_source/devices";
+
+/// A PMU's properties, as published in sysfs.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Pmu {
+    /// The PMU's name: the name of its sysfs directory.
+    pub name: String,
+
+    /// The PMU's sysfs directory.
+    pub path: PathBuf,
+
+    /// The PMU's type number, for `perf_event_attr::type_`.
+    pub type_: u32,
+
+    /// For uncore and other system-wide PMUs, the CPUs on which events should
+    /// be opened, in the kernel's CPU list format.
+    pub cpumask: Option<String>,
+
+    /// For core PMUs on hybrid systems, the CPUs the PMU covers, in the
+    /// kernel's CPU list format.
+    pub cpus: Option<String>,
+
+    /// The number of address range filters the PMU supports.
+    pub nr_addr_filters: Option<u32>,
+
+    /// The interval, in milliseconds, at which the PMU rotates multiplexed
+    /// events.
+    pub mux_interval_ms: Option<u32>,
+
+    /// The contents of the files in the PMU's `caps` directory, keyed by
+    /// file name.
+    pub caps: BTreeMap<String, String>,
+}
+
+impl Pmu {
+    /// Read the PMU whose sysfs directory is `path`.
+    pub fn load(path: &Path) -> io::Result<Pmu> {
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+
+        let mut caps = BTreeMap::new();
+        match fs::read_dir(path.join("caps")) {
+            Ok(entries) => {
+                for entry in entries {
+                    let entry = entry?;
+                    let value = fs::read_to_string(entry.path())?;
+                    caps.insert(
+                        entry.file_name().to_string_lossy().into_owned(),
+                        value.trim().to_string(),
+                    );
+                }
+            }
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+            Err(err) => return Err(err),
+        }
+
+        Ok(Pmu {
+            name,
+            path: path.to_owned(),
+            type_: parse_number(&fs::read_to_string(path.join("type"))?)?,
+            cpumask: read_optional(&path.join("cpumask"))?,
+            cpus: read_optional(&path.join("cpus"))?,
+            nr_addr_filters: read_optional(&path.join("nr_addr_filters"))?
+                .map(|text| parse_number(&text))
+                .transpose()?,
+            mux_interval_ms: read_optional(&path.join("perf_event_mux_interval_ms"))?
+                .map(|text| parse_number(&text))
+                .transpose()?,
+            caps,
+        })
+    }
+
+    /// Set `attrs.type_` to select this PMU.
+    pub fn set_type(&self, attrs: &mut perf_event_attr) {
+        attrs.type_ = self.type_;
+    }
+}
+
+/// The PMUs available on the system, by name.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PmuRegistry {
+    pmus: BTreeMap<String, Pmu>,
+
+    /// Directories that couldn't be read as PMUs, with the reason.
+    skipped: BTreeMap<String, String>,
+}
+
+impl PmuRegistry {
+    /// Read every PMU listed under [`SYSFS_PMU_ROOT`].
+    pub fn load() -> io::Result<PmuRegistry> {
+        PmuRegistry::load_from(Path::new(SYSFS_PMU_ROOT))
+    }
+
+    /// Read every PMU listed under `root`, which should have the same layout
+    /// as [`SYSFS_PMU_ROOT`].
+    ///
+    /// A PMU whose directory can't be read, or has a malformed attribute, is
+    /// left out of the registry, and listed by [`PmuRegistry::skipped`]
+    /// instead; only a failure to list `root` itself is an error.
+    pub fn load_from(root: &Path) -> io::Result<PmuRegistry> {
+        let mut registry = PmuRegistry::default();
+        for entry in fs::read_dir(root)? {
+            let entry = entry?;
+            match Pmu::load(&entry.path()) {
+                Ok(pmu) => {
+                    registry.pmus.insert(pmu.name.clone(), pmu);
+                }
+                Err(err) => {
+                    let name = entry.file_name().to_string_lossy().into_owned();
+                    registry.skipped.insert(name, err.to_string());
+                }
+            }
+        }
+        Ok(registry)
+    }
+
+    /// Return the PMU named `name`, if there is one.
+    pub fn get(&self, name: &str) -> Option<&Pmu> {
+        self.pmus.get(name)
+    }
+
+    /// Return an iterator over the PMUs, in order by name.
+    pub fn iter(&self) -> impl Iterator<Item = &Pmu> {
+        self.pmus.values()
+    }
+
+    /// Return an iterator over the directories that couldn't be read as
+    /// PMUs, in order by name, with a description of the problem.
+    pub fn skipped(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.skipped
+            .iter()
+            .map(|(name, reason)| (name.as_str(), reason.as_str()))
+    }
+
+    /// Set `attrs.type_` to select the PMU named `name`.
+    ///
+    /// Return an error of kind `NotFound` if there is no such PMU.
+    pub fn set_type(&self, attrs: &mut perf_event_attr, name: &str) -> io::Result<()> {
+        match self.get(name) {
+            Some(pmu) => {
+                pmu.set_type(attrs);
+                Ok(())
+            }
+            None => Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("no PMU named {:?}", name),
+            )),
+        }
+    }
+}
+
+/// Read the sysfs attribute `path`, trimmed, or return `None` if it doesn't
+/// exist.
+pub(crate) fn read_optional(path: &Path) -> io::Result<Option<String>> {
+    match fs::read_to_string(path) {
+        Ok(text) => Ok(Some(text.trim().to_string())),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(err),
+    }
+}
+
+fn parse_number(text: &str) -> io::Result<u32> {
+    text.trim()
+        .parse()
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempTree;
+
+    #[test]
+    fn fake_tree() {
+        let tree = TempTree::new("pmu");
+        tree.write("software/type", "1\n");
+        tree.write("software/perf_event_mux_interval_ms", "4\n");
+        tree.write("intel_pt/type", "10\n");
+        tree.write("intel_pt/nr_addr_filters", "2\n");
+        tree.write("intel_pt/caps/psb_cyc", "1\n");
+        tree.write("intel_pt/caps/topa_output", "1\n");
+        tree.write("uncore_imc_0/type", "17\n");
+        tree.write("uncore_imc_0/cpumask", "0,18\n");
+        tree.write("broken/type", "not a number\n");
+        tree.write("power", "not a directory\n");
+
+        let pmus = PmuRegistry::load_from(tree.path()).unwrap();
+        let skipped: Vec<&str> = pmus.skipped().map(|(name, _)| name).collect();
+        assert_eq!(skipped, ["broken", "power"]);
+
+        let names: Vec<&str> = pmus.iter().map(|pmu| pmu.name.as_str()).collect();
+        assert_eq!(names, ["intel_pt", "software", "uncore_imc_0"]);
+
+        let pt = pmus.get("intel_pt").unwrap();
+        assert_eq!(pt.type_, 10);
+        assert_eq!(pt.nr_addr_filters, Some(2));
+        assert_eq!(pt.caps.get("psb_cyc").map(String::as_str), Some("1"));
+        assert_eq!(pt.caps.len(), 2);
+        assert_eq!(pt.cpumask, None);
+
+        assert_eq!(pmus.get("software").unwrap().mux_interval_ms, Some(4));
+        assert_eq!(
+            pmus.get("uncore_imc_0").unwrap().cpumask.as_deref(),
+            Some("0,18")
+        );
+
+        let mut attrs = perf_event_attr::default();
+        pmus.set_type(&mut attrs, "uncore_imc_0").unwrap();
+        assert_eq!(attrs.type_, 17);
+        assert_eq!(
+            pmus.set_type(&mut attrs, "nonesuch").unwrap_err().kind(),
+            io::ErrorKind::NotFound
+        );
+    }
+}
@@ -0,0 +1,689 @@
+//! PMU config formats, event aliases, and perf-style event strings.
+//!
+//! Each PMU describes how its events' parameters are packed into the
+//! `config`, `config1` and `config2` fields of `perf_event_attr` with one
+//! file per parameter in its sysfs `format` directory:
+//!
+//! ```text
+//! /sys/bus/event_source/devices/cpu/format/event    config:0-7
+//! /sys/bus/event_source/devices/cpu/format/umask    config:8-15
+//! /sys/bus/event_source/devices/cpu/format/cmask    config:24-31
+//! /sys/bus/event_source/devices/cpu/format/ldlat    config1:0-15
+//! ```
+//!
+//! A parameter may be split across several bit ranges, like `config:0-7,32-35`;
+//! the low bits of its value go in the first range, the next bits in the
+//! second, and so on. [`FormatField`] parses these, and [`PmuFormat`] holds
+//! all of a PMU's fields.
+//!
+//! A PMU may also name some of its events in its `events` directory. Each
+//! file holds a list of parameter settings, like `event=0x02`, and may be
+//! accompanied by `.scale` and `.unit` files giving the units of the count.
+//! [`EventAlias`] holds one of these.
+//!
+//! Finally, [`compile`] turns an event string in the syntax the `perf` tool
+//! accepts into a `perf_event_attr`:
+//!
+//! ```no_run
+//! use perf_event_open_sys::pmu::PmuRegistry;
+//! use perf_event_open_sys::pmu_event;
+//!
+//! let pmus = PmuRegistry::load()?;
+//! let cycles = pmu_event::compile("cpu/event=0x3c,umask=0x0,cmask=1/", &pmus)?;
+//! let energy = pmu_event::compile("power/energy-pkg/", &pmus)?;
+//! # Ok::<(), pmu_event::EventError>(())
+//! ```
+//!
+//! The terms between the slashes are comma-separated. Each is either
+//! `name=value`, setting a format field, or a bare `name`, which expands an
+//! event alias or, failing that, sets a format field to 1. The names `config`,
+//! `config1` and `config2` refer to the whole field, unless the PMU's format
+//! defines them otherwise. An explicit term overrides an alias's setting of the
+//! same field, so `cpu/mem-loads,ldlat=5/` uses the alias with a different
+//! latency threshold. Values may be decimal or `0x`-prefixed hex.
+
+use crate::bindings::perf_event_attr;
+use crate::pmu::{Pmu, PmuRegistry};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// Which `perf_event_attr` field a format field's bits live in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ConfigWord {
+    /// `perf_event_attr::config`.
+    Config,
+
+    /// `config1`, which shares a union with `bp_addr`, `kprobe_func` and
+    /// `uprobe_path`.
+    Config1,
+
+    /// `config2`, which shares a union with `bp_len`, `kprobe_addr` and
+    /// `probe_offset`.
+    Config2,
+}
+
+impl ConfigWord {
+    fn index(self) -> usize {
+        match self {
+            ConfigWord::Config => 0,
+            ConfigWord::Config1 => 1,
+            ConfigWord::Config2 => 2,
+        }
+    }
+
+    fn from_name(name: &str) -> Option<ConfigWord> {
+        match name {
+            "config" => Some(ConfigWord::Config),
+            "config1" => Some(ConfigWord::Config1),
+            "config2" => Some(ConfigWord::Config2),
+            _ => None,
+        }
+    }
+}
+
+/// The layout of one parameter of a PMU's events, from its `format`
+/// directory.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FormatField {
+    /// The field holding the parameter's bits.
+    pub word: ConfigWord,
+
+    /// The inclusive bit ranges holding the parameter, low-order bits of the
+    /// value first.
+    pub ranges: Vec<(u32, u32)>,
+}
+
+impl FormatField {
+    /// Parse a format description like `config:0-7` or `config1:0-7,32-35`.
+    pub fn parse(text: &str) -> Option<FormatField> {
+        let (word, bits) = text.trim().split_once(':')?;
+        let word = ConfigWord::from_name(word)?;
+        let ranges = bits
+            .split(',')
+            .map(|range| {
+                let (lo, hi) = match range.split_once('-') {
+                    Some((lo, hi)) => (lo.parse().ok()?, hi.parse().ok()?),
+                    None => {
+                        let bit = range.parse().ok()?;
+                        (bit, bit)
+                    }
+                };
+                if lo > hi || hi > 63 {
+                    return None;
+                }
+                Some((lo, hi))
+            })
+            .collect::<Option<Vec<_>>>()?;
+        Some(FormatField { word, ranges })
+    }
+
+    /// A field occupying all 64 bits of `word`.
+    fn whole(word: ConfigWord) -> FormatField {
+        FormatField {
+            word,
+            ranges: vec![(0, 63)],
+        }
+    }
+
+    /// Return the number of bits available for the parameter's value.
+    pub fn width(&self) -> u32 {
+        self.ranges.iter().map(|&(lo, hi)| hi - lo + 1).sum()
+    }
+
+    /// Return a mask of the bits this field occupies in its word.
+    pub fn mask(&self) -> u64 {
+        self.ranges
+            .iter()
+            .fold(0, |mask, &(lo, hi)| mask | range_mask(lo, hi))
+    }
+
+    /// Spread `value` across this field's bit ranges, returning the bits to
+    /// be or-ed into its word, or `None` if `value` doesn't fit.
+    pub fn encode(&self, value: u64) -> Option<u64> {
+        let mut rest = value;
+        let mut bits = 0;
+        for &(lo, hi) in &self.ranges {
+            let width = hi - lo + 1;
+            bits |= (rest & range_mask(0, width - 1)) << lo;
+            rest = rest.checked_shr(width).unwrap_or(0);
+        }
+        if rest == 0 {
+            Some(bits)
+        } else {
+            None
+        }
+    }
+}
+
+fn range_mask(lo: u32, hi: u32) -> u64 {
+    (u64::MAX >> (63 - hi)) & (u64::MAX << lo)
+}
+
+/// The parameters of a PMU's events, from its `format` directory.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PmuFormat {
+    pub fields: BTreeMap<String, FormatField>,
+}
+
+impl PmuFormat {
+    /// Read the `format` directory of `pmu`. A PMU without one has no
+    /// fields.
+    pub fn load(pmu: &Pmu) -> Result<PmuFormat, EventError> {
+        let mut fields = BTreeMap::new();
+        for (name, text) in read_dir_files(&pmu.path.join("format"))? {
+            let field = FormatField::parse(&text).ok_or_else(|| EventError::BadFormat {
+                field: name.clone(),
+                text: text.clone(),
+            })?;
+            fields.insert(name, field);
+        }
+        Ok(PmuFormat { fields })
+    }
+
+    /// Return the field named `name`. The names `config`, `config1` and
+    /// `config2` refer to the whole word, if the format doesn't define them.
+    pub fn field(&self, name: &str) -> Option<FormatField> {
+        match self.fields.get(name) {
+            Some(field) => Some(field.clone()),
+            None => ConfigWord::from_name(name).map(FormatField::whole),
+        }
+    }
+}
+
+/// One `name` or `name=value` term of an event string or alias.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Term {
+    pub name: String,
+    pub value: Option<u64>,
+}
+
+/// Parse a comma-separated list of terms, like `event=0x3c,umask=0x0,edge`.
+pub fn parse_terms(text: &str) -> Result<Vec<Term>, EventError> {
+    text.split(',')
+        .map(str::trim)
+        .filter(|term| !term.is_empty())
+        .map(|term| match term.split_once('=') {
+            Some((name, value)) => Ok(Term {
+                name: name.trim().to_string(),
+                value: Some(
+                    parse_value(value.trim())
+                        .ok_or_else(|| EventError::Syntax(term.to_string()))?,
+                ),
+            }),
+            None => Ok(Term {
+                name: term.to_string(),
+                value: None,
+            }),
+        })
+        .collect()
+}
+
+fn parse_value(text: &str) -> Option<u64> {
+    match text.strip_prefix("0x").or_else(|| text.strip_prefix("0X")) {
+        Some(hex) => u64::from_str_radix(hex, 16).ok(),
+        None => text.parse().ok(),
+    }
+}
+
+/// A named event from a PMU's `events` directory.
+#[derive(Clone, Debug, PartialEq)]
+pub struct EventAlias {
+    /// The terms the alias stands for.
+    pub terms: Vec<Term>,
+
+    /// The factor by which to multiply counts to get values in `unit`, from
+    /// the alias's `.scale` file.
+    pub scale: Option<f64>,
+
+    /// The unit of the scaled count, like `Joules`, from the alias's `.unit`
+    /// file.
+    pub unit: Option<String>,
+}
+
+/// The event aliases from a PMU's `events` directory.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct EventAliases {
+    aliases: BTreeMap<String, EventAlias>,
+
+    /// Aliases that couldn't be read or parsed, with the reason.
+    skipped: BTreeMap<String, String>,
+}
+
+impl EventAliases {
+    /// Return the alias named `name`, if there is one.
+    pub fn get(&self, name: &str) -> Option<&EventAlias> {
+        self.aliases.get(name)
+    }
+
+    /// Return an iterator over the aliases, in order by name.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &EventAlias)> {
+        self.aliases
+            .iter()
+            .map(|(name, alias)| (name.as_str(), alias))
+    }
+
+    /// Return an iterator over the aliases that couldn't be read or parsed,
+    /// in order by name, with a description of the problem.
+    pub fn skipped(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.skipped
+            .iter()
+            .map(|(name, reason)| (name.as_str(), reason.as_str()))
+    }
+}
+
+/// Read the `events` directory of `pmu`. A PMU without one has no aliases.
+///
+/// Each file is read once. An alias whose files can't be read, or are
+/// malformed, is left out, and listed by [`EventAliases::skipped`] instead;
+/// only a failure to list the directory itself is an error.
+pub fn load_events(pmu: &Pmu) -> Result<EventAliases, EventError> {
+    let mut files = BTreeMap::new();
+    let entries = match fs::read_dir(pmu.path.join("events")) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(EventAliases::default()),
+        Err(err) => return Err(err.into()),
+    };
+    for entry in entries {
+        let entry = entry?;
+        let text = fs::read_to_string(entry.path()).map(|text| text.trim().to_string());
+        files.insert(entry.file_name().to_string_lossy().into_owned(), text);
+    }
+
+    let mut events = EventAliases::default();
+    for name in files.keys() {
+        if name.contains('.') {
+            continue;
+        }
+        match alias_from_files(&files, name) {
+            Ok(alias) => {
+                events.aliases.insert(name.clone(), alias);
+            }
+            Err(err) => {
+                events.skipped.insert(name.clone(), err.to_string());
+            }
+        }
+    }
+    Ok(events)
+}
+
+/// Build the alias `name` from the already-read contents of an `events`
+/// directory.
+fn alias_from_files(
+    files: &BTreeMap<String, io::Result<String>>,
+    name: &str,
+) -> Result<EventAlias, EventError> {
+    let get = |file: &str| match files.get(file) {
+        Some(Ok(text)) => Ok(Some(text.as_str())),
+        Some(Err(err)) => Err(io::Error::new(err.kind(), err.to_string())),
+        None => Ok(None),
+    };
+    let text = get(name)?.unwrap_or_default();
+    let scale = get(&format!("{}.scale", name))?;
+    let unit = get(&format!("{}.unit", name))?;
+    parse_alias(text, scale, unit)
+}
+
+/// Read the alias `name` from the `events` directory of `pmu`, or return
+/// `None` if there is no such alias.
+///
+/// Only this alias's files are read, so a malformed alias elsewhere in the
+/// directory doesn't matter.
+pub fn load_event(pmu: &Pmu, name: &str) -> Result<Option<EventAlias>, EventError> {
+    if name.is_empty() || name.contains(['.', '/']) {
+        return Ok(None);
+    }
+    let dir = pmu.path.join("events");
+    let text = match read_file(&dir.join(name))? {
+        Some(text) => text,
+        None => return Ok(None),
+    };
+    let scale = read_file(&dir.join(format!("{}.scale", name)))?;
+    let unit = read_file(&dir.join(format!("{}.unit", name)))?;
+    parse_alias(&text, scale.as_deref(), unit.as_deref()).map(Some)
+}
+
+/// Build an alias from the contents of its file and its `.scale` and `.unit`
+/// files, if it has them.
+fn parse_alias(
+    text: &str,
+    scale: Option<&str>,
+    unit: Option<&str>,
+) -> Result<EventAlias, EventError> {
+    let scale = match scale {
+        Some(scale) => Some(
+            scale
+                .parse()
+                .map_err(|_| EventError::Syntax(scale.to_string()))?,
+        ),
+        None => None,
+    };
+    Ok(EventAlias {
+        terms: parse_terms(text)?,
+        scale,
+        unit: unit.map(str::to_string),
+    })
+}
+
+/// Return the trimmed contents of `path`, or `None` if it doesn't exist.
+fn read_file(path: &Path) -> io::Result<Option<String>> {
+    match fs::read_to_string(path) {
+        Ok(text) => Ok(Some(text.trim().to_string())),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(err),
+    }
+}
+
+/// Return the trimmed contents of every file in `dir`, by name, or an empty
+/// map if `dir` doesn't exist.
+fn read_dir_files(dir: &Path) -> io::Result<BTreeMap<String, String>> {
+    let mut files = BTreeMap::new();
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(files),
+        Err(err) => return Err(err),
+    };
+    for entry in entries {
+        let entry = entry?;
+        let text = fs::read_to_string(entry.path())?;
+        files.insert(
+            entry.file_name().to_string_lossy().into_owned(),
+            text.trim().to_string(),
+        );
+    }
+    Ok(files)
+}
+
+/// Compile a perf-style event string like `cpu/event=0x3c,umask=0x0/` or
+/// `power/energy-pkg/` into a `perf_event_attr`, looking up the PMU in
+/// `pmus`.
+///
+/// The result has `size`, `type_`, `config`, `config1` and `config2` filled
+/// in, and all other fields zero.
+pub fn compile(event: &str, pmus: &PmuRegistry) -> Result<perf_event_attr, EventError> {
+    let syntax = || EventError::Syntax(event.to_string());
+    let (pmu_name, terms) = event
+        .trim()
+        .strip_suffix('/')
+        .and_then(|rest| rest.split_once('/'))
+        .ok_or_else(syntax)?;
+    if terms.contains('/') {
+        return Err(syntax());
+    }
+    let pmu = pmus
+        .get(pmu_name)
+        .ok_or_else(|| EventError::UnknownPmu(pmu_name.to_string()))?;
+    let format = PmuFormat::load(pmu)?;
+
+    // Expand bare terms naming an alias. The alias's terms are defaults: an
+    // explicit term for the same field overrides them, as in perf.
+    let mut expanded = vec![];
+    for term in parse_terms(terms)? {
+        let alias = match term.value {
+            None => load_event(pmu, &term.name)?,
+            Some(_) => None,
+        };
+        match alias {
+            Some(alias) => expanded.push((alias.terms, true)),
+            None => expanded.push((vec![term], false)),
+        }
+    }
+    let explicit: Vec<String> = expanded
+        .iter()
+        .filter(|(_, from_alias)| !from_alias)
+        .flat_map(|(terms, _)| terms.iter().map(|term| term.name.clone()))
+        .collect();
+
+    let mut encoder = Encoder {
+        pmu: pmu_name,
+        format: &format,
+        words: [0; 3],
+        owners: vec![],
+    };
+    for (terms, from_alias) in &expanded {
+        for term in terms {
+            if *from_alias && explicit.contains(&term.name) {
+                continue;
+            }
+            encoder.set(&term.name, term.value.unwrap_or(1))?;
+        }
+    }
+
+    let mut attrs = perf_event_attr {
+        size: std::mem::size_of::<perf_event_attr>() as u32,
+        ..Default::default()
+    };
+    pmu.set_type(&mut attrs);
+    attrs.config = encoder.words[0];
+    attrs.set_config1(encoder.words[1]);
+    attrs.set_config2(encoder.words[2]);
+    Ok(attrs)
+}
+
+/// Accumulates format field values into the three config words, checking
+/// for overflow and overlap.
+struct Encoder<'a> {
+    pmu: &'a str,
+    format: &'a PmuFormat,
+    words: [u64; 3],
+
+    /// Each field set so far, with its word index and mask.
+    owners: Vec<(String, usize, u64)>,
+}
+
+impl Encoder<'_> {
+    fn set(&mut self, name: &str, value: u64) -> Result<(), EventError> {
+        let field = self
+            .format
+            .field(name)
+            .ok_or_else(|| EventError::UnknownTerm {
+                pmu: self.pmu.to_string(),
+                term: name.to_string(),
+            })?;
+        let bits = field.encode(value).ok_or_else(|| EventError::Overflow {
+            term: name.to_string(),
+            value,
+            width: field.width(),
+        })?;
+
+        let word = field.word.index();
+        let mask = field.mask();
+        if let Some((other, ..)) = self
+            .owners
+            .iter()
+            .find(|&&(_, other_word, other_mask)| other_word == word && other_mask & mask != 0)
+        {
+            return Err(EventError::Overlap {
+                term: name.to_string(),
+                other: other.clone(),
+            });
+        }
+
+        self.words[word] |= bits;
+        self.owners.push((name.to_string(), word, mask));
+        Ok(())
+    }
+}
+
+/// An error parsing a PMU's sysfs descriptions or compiling an event string.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum EventError {
+    /// Reading sysfs failed.
+    Io(io::Error),
+
+    /// An event string, term, or scale was malformed.
+    Syntax(String),
+
+    /// A file in a PMU's `format` directory was malformed.
+    BadFormat { field: String, text: String },
+
+    /// No PMU has the given name.
+    UnknownPmu(String),
+
+    /// The PMU has no format field or event alias with the given name.
+    UnknownTerm { pmu: String, term: String },
+
+    /// A value was too large for its field.
+    Overflow {
+        term: String,
+        value: u64,
+        width: u32,
+    },
+
+    /// Two terms set some of the same bits.
+    Overlap { term: String, other: String },
+}
+
+impl fmt::Display for EventError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            EventError::Io(err) => err.fmt(f),
+            EventError::Syntax(text) => write!(f, "malformed event description: {:?}", text),
+            EventError::BadFormat { field, text } => {
+                write!(f, "malformed format for field {:?}: {:?}", field, text)
+            }
+            EventError::UnknownPmu(pmu) => write!(f, "no PMU named {:?}", pmu),
+            EventError::UnknownTerm { pmu, term } => {
+                write!(f, "PMU {:?} has no field or event named {:?}", pmu, term)
+            }
+            EventError::Overflow { term, value, width } => write!(
+                f,
+                "value {:#x} for {:?} doesn't fit in {} bits",
+                value, term, width
+            ),
+            EventError::Overlap { term, other } => {
+                write!(f, "{:?} overlaps bits already set by {:?}", term, other)
+            }
+        }
+    }
+}
+
+impl std::error::Error for EventError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            EventError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for EventError {
+    fn from(err: io::Error) -> EventError {
+        EventError::Io(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_fields() {
+        let field = FormatField::parse("config1:0-7,32-35\n").unwrap();
+        assert_eq!(field.word, ConfigWord::Config1);
+        assert_eq!(field.width(), 12);
+        assert_eq!(field.mask(), 0xf_0000_00ff);
+        assert_eq!(field.encode(0xabc), Some(0xa_0000_00bc));
+        assert_eq!(field.encode(0x1000), None);
+
+        assert_eq!(FormatField::parse("config:21").unwrap().mask(), 1 << 21);
+        assert_eq!(
+            FormatField::whole(ConfigWord::Config).encode(u64::MAX),
+            Some(u64::MAX)
+        );
+        assert_eq!(FormatField::parse("config3:0-7"), None);
+        assert_eq!(FormatField::parse("config:7-0"), None);
+        assert_eq!(FormatField::parse("config:0-64"), None);
+    }
+
+    #[test]
+    fn compile_strings() {
+        let root = crate::test_util::TempTree::new("pmu-event");
+        let write = |path: &str, contents: &str| root.write(path, contents);
+        write("cpu/type", "4\n");
+        write("cpu/format/event", "config:0-7\n");
+        write("cpu/format/umask", "config:8-15\n");
+        write("cpu/format/edge", "config:18\n");
+        write("cpu/format/cmask", "config:24-31\n");
+        write("cpu/format/ldlat", "config1:0-15\n");
+        write("cpu/format/overlap", "config:4-11\n");
+        write("cpu/events/mem-loads", "event=0xcd,umask=0x1,ldlat=3\n");
+        // A broken alias only matters to event strings that use it.
+        write("cpu/events/broken", "event=bogus\n");
+        // Reading a directory fails, as an unreadable alias file would.
+        write("cpu/events/unreadable/x", "");
+        write("power/type", "23\n");
+        write("power/format/event", "config:0-7\n");
+        write("power/events/energy-pkg", "event=0x02\n");
+        write(
+            "power/events/energy-pkg.scale",
+            "2.3283064365386962890625e-10\n",
+        );
+        write("power/events/energy-pkg.unit", "Joules\n");
+
+        let pmus = PmuRegistry::load_from(root.path()).unwrap();
+        let result = (|| -> Result<_, EventError> {
+            Ok((
+                compile("cpu/event=0x3c,umask=0x0,cmask=1/", &pmus)?,
+                compile("cpu/mem-loads,edge/", &pmus)?,
+                compile("cpu/ldlat=5,mem-loads/", &pmus)?,
+                compile("power/energy-pkg/", &pmus)?,
+                load_events(pmus.get("cpu").unwrap())?,
+                load_events(pmus.get("power").unwrap())?,
+            ))
+        })();
+        let errors = [
+            compile("cpu/event=0x100/", &pmus),
+            compile("cpu/event=1,overlap=1/", &pmus),
+            compile("cpu/nonesuch/", &pmus),
+            compile("gpu/event=1/", &pmus),
+            compile("cpu/event=1", &pmus),
+            compile("cpu/broken/", &pmus),
+            compile("cpu/mem-loads,overlap=1/", &pmus),
+        ];
+        let (cycles, loads, loads_5, energy, cpu_events, power_events) = result.unwrap();
+
+        assert_eq!(cycles.type_, 4);
+        assert_eq!(cycles.config, 0x0100_003c);
+
+        assert_eq!(loads.config, 1 << 18 | 0x01cd);
+        assert_eq!(loads.config1(), 3);
+        assert_eq!(loads.config2(), 0);
+        assert_eq!(loads_5.config, 0x01cd);
+        assert_eq!(loads_5.config1(), 5);
+
+        assert_eq!(energy.type_, 23);
+        assert_eq!(energy.config, 2);
+        let names: Vec<_> = cpu_events.iter().map(|(name, _)| name).collect();
+        assert_eq!(names, ["mem-loads"]);
+        let skipped: Vec<_> = cpu_events.skipped().map(|(name, _)| name).collect();
+        assert_eq!(skipped, ["broken", "unreadable"]);
+        let alias = power_events.get("energy-pkg").unwrap();
+        assert_eq!(alias.unit.as_deref(), Some("Joules"));
+        assert_eq!(alias.scale, Some(1.0 / (1_u64 << 32) as f64));
+
+        assert!(matches!(
+            errors[0],
+            Err(EventError::Overflow {
+                width: 8,
+                value: 0x100,
+                ..
+            })
+        ));
+        assert!(matches!(
+            &errors[1],
+            Err(EventError::Overlap { term, other }) if term == "overlap" && other == "event"
+        ));
+        assert!(matches!(errors[2], Err(EventError::UnknownTerm { .. })));
+        assert!(matches!(errors[3], Err(EventError::UnknownPmu(_))));
+        assert!(matches!(errors[4], Err(EventError::Syntax(_))));
+        assert!(matches!(errors[5], Err(EventError::Syntax(_))));
+        assert!(matches!(
+            &errors[6],
+            Err(EventError::Overlap { term, other }) if term == "overlap" && other == "event"
+        ));
+    }
+}
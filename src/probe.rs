@@ -0,0 +1,420 @@
+//! Creating kprobe and uprobe events with the `kprobe` and `uprobe` PMUs.
+//!
+//! Since Linux 4.17, a kprobe can be created directly by `perf_event_open`,
+//! without going through tracefs, by opening an event on the dynamic `kprobe`
+//! PMU. The probe location is given either as a symbol name and offset, with
+//! `kprobe_func` pointing to the NUL-terminated name and `probe_offset`
+//! holding the offset, or as a raw kernel address in `kprobe_addr`. To probe
+//! function returns instead, set the PMU's `retprobe` format bit in `config`.
+//!
+//! A [`KprobeSpec`] describes such a probe, and owns the symbol name's
+//! storage. Its [`attr`] method returns a [`ProbeAttr`], which borrows the
+//! spec, so the `perf_event_attr` it holds can't outlive the string it points
+//! to:
+//!
+//! ```no_run
+//! use perf_event_open_sys::pmu::PmuRegistry;
+//! use perf_event_open_sys::probe::KprobeSpec;
+//!
+//! let pmus = PmuRegistry::load()?;
+//! let spec = KprobeSpec::symbol("do_sys_openat2", 0)?.retprobe(true);
+//! let mut attr = spec.attr(&pmus)?;
+//! attr.set_sample_period(1);
+//! let fd = unsafe { attr.open(-1, 0, -1, 0)? };
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+//!
+//! A [`UprobeSpec`] does the same for uprobes on the `uprobe` PMU, which take
+//! a path in `uprobe_path` and a *file offset* in `probe_offset`. Since
+//! symbol tables hold virtual addresses, [`UprobeSpec::new`] reads the ELF
+//! file, looks the symbol up in `.symtab` or `.dynsym`, and converts its
+//! address to a file offset using the program headers:
+//!
+//! ```no_run
+//! # use perf_event_open_sys::pmu::PmuRegistry;
+//! use perf_event_open_sys::probe::UprobeSpec;
+//!
+//! # let pmus = PmuRegistry::load()?;
+//! let spec = UprobeSpec::new("/usr/lib64/libc.so.6", "malloc")?;
+//! let attr = spec.attr(&pmus)?;
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+//!
+//! [`attr`]: KprobeSpec::attr
+
+use crate::bindings::perf_event_attr;
+use crate::error::PerfOpenError;
+use crate::perf_event_open_fd;
+use crate::pmu::PmuRegistry;
+use crate::pmu_event::{EventError, PmuFormat};
+use libc::{c_int, c_ulong, pid_t};
+use std::ffi::{CString, NulError};
+use std::fs;
+use std::io;
+use std::marker::PhantomData;
+use std::os::unix::ffi::OsStrExt;
+use std::os::unix::io::OwnedFd;
+use std::path::Path;
+
+mod elf;
+
+/// A `perf_event_attr` for a probe, which may point into the spec that
+/// produced it.
+///
+/// The attr is only handed out through [`ProbeAttr::open`],
+/// [`ProbeAttr::attr_mut`] and [`ProbeAttr::as_ptr`], all of which borrow the
+/// `ProbeAttr`, so it can't be used after the spec is dropped without
+/// deliberately copying it out. The fields that don't affect the probe
+/// location have setters; [`ProbeAttr::attr_mut`] reaches the rest, and lets
+/// the attr be passed to functions like [`Group::new`] that take a
+/// `&mut perf_event_attr`.
+///
+/// [`Group::new`]: crate::group::Group::new
+pub struct ProbeAttr<'a> {
+    attr: perf_event_attr,
+    _spec: PhantomData<&'a ()>,
+}
+
+macro_rules! define_setters {
+    ( $( $(#[$doc:meta])* $setter:ident: $ty:ty; )* ) => {
+        $(
+            $(#[$doc])*
+            pub fn $setter(&mut self, value: $ty) {
+                self.attr.$setter(value);
+            }
+        )*
+    }
+}
+
+impl ProbeAttr<'_> {
+    define_setters! {
+        /// Set `sample_period`.
+        set_sample_period: u64;
+        /// Set the `disabled` flag.
+        set_disabled_flag: bool;
+        /// Set the `inherit` flag.
+        set_inherit_flag: bool;
+        /// Set the `exclude_kernel` flag.
+        set_exclude_kernel_flag: bool;
+        /// Set the `exclude_hv` flag.
+        set_exclude_hv_flag: bool;
+    }
+
+    /// Set `sample_type`.
+    pub fn set_sample_type(&mut self, sample_type: u64) {
+        self.attr.sample_type = sample_type;
+    }
+
+    /// Set `read_format`.
+    pub fn set_read_format(&mut self, read_format: u64) {
+        self.attr.read_format = read_format;
+    }
+
+    /// Open the probe, with the same arguments as [`perf_event_open_fd`].
+    ///
+    /// # Safety
+    ///
+    /// The measurements and other behaviors requested by the attr must be
+    /// safe.
+    pub unsafe fn open(
+        &self,
+        pid: pid_t,
+        cpu: c_int,
+        group_fd: c_int,
+        flags: c_ulong,
+    ) -> Result<OwnedFd, PerfOpenError> {
+        let mut attr = self.attr;
+        perf_event_open_fd(&mut attr, pid, cpu, group_fd, flags)
+    }
+
+    /// Return the attr, borrowing `self` for as long as it's used.
+    ///
+    /// Changing `type_`, `config`, or the probe location fields retargets the
+    /// probe; in particular, `kprobe_func` and `uprobe_path` must still point
+    /// to a NUL-terminated string that outlives the attr's use.
+    pub fn attr_mut(&mut self) -> &mut perf_event_attr {
+        &mut self.attr
+    }
+
+    /// Return a pointer to the attr, valid for as long as `self` is
+    /// borrowed.
+    pub fn as_ptr(&mut self) -> *mut perf_event_attr {
+        &mut self.attr
+    }
+}
+
+/// Where a kprobe is placed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum KprobeTarget {
+    /// An offset from a kernel symbol.
+    Symbol { name: CString, offset: u64 },
+
+    /// A raw kernel address.
+    Address(u64),
+}
+
+/// A kprobe or kretprobe to create with the `kprobe` PMU.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KprobeSpec {
+    pub target: KprobeTarget,
+    pub retprobe: bool,
+}
+
+impl KprobeSpec {
+    /// Probe `offset` bytes into the kernel function `name`.
+    ///
+    /// Return an error if `name` contains a NUL byte.
+    pub fn symbol(name: &str, offset: u64) -> Result<KprobeSpec, NulError> {
+        Ok(KprobeSpec {
+            target: KprobeTarget::Symbol {
+                name: CString::new(name)?,
+                offset,
+            },
+            retprobe: false,
+        })
+    }
+
+    /// Probe the kernel address `addr`.
+    pub fn address(addr: u64) -> KprobeSpec {
+        KprobeSpec {
+            target: KprobeTarget::Address(addr),
+            retprobe: false,
+        }
+    }
+
+    /// Set whether to probe function returns, rather than the probe location
+    /// itself.
+    pub fn retprobe(mut self, retprobe: bool) -> KprobeSpec {
+        self.retprobe = retprobe;
+        self
+    }
+
+    /// Return a `perf_event_attr` for this probe, using the `kprobe` PMU in
+    /// `pmus`.
+    ///
+    /// The result has `size`, `type_`, `config`, and the probe location
+    /// fields set, and all other fields zero.
+    pub fn attr(&self, pmus: &PmuRegistry) -> Result<ProbeAttr<'_>, EventError> {
+        let mut attr = probe_attr(pmus, "kprobe", self.retprobe)?;
+        match &self.target {
+            KprobeTarget::Symbol { name, offset } => {
+                attr.set_kprobe_func(name.as_ptr() as u64);
+                attr.set_probe_offset(*offset);
+            }
+            KprobeTarget::Address(addr) => {
+                attr.set_kprobe_func(0);
+                attr.set_kprobe_addr(*addr);
+            }
+        }
+        Ok(ProbeAttr {
+            attr,
+            _spec: PhantomData,
+        })
+    }
+}
+
+/// A uprobe or uretprobe to create with the `uprobe` PMU.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UprobeSpec {
+    /// The path of the file to probe.
+    pub path: CString,
+
+    /// The file offset of the probe location.
+    pub offset: u64,
+
+    pub retprobe: bool,
+}
+
+impl UprobeSpec {
+    /// Probe the function `symbol` in the ELF file at `path`.
+    ///
+    /// The symbol is looked up in the file's `.symtab` section, and then in
+    /// its `.dynsym` section. If it isn't found, return an error of kind
+    /// `NotFound`.
+    pub fn new<P: AsRef<Path>>(path: P, symbol: &str) -> io::Result<UprobeSpec> {
+        let path = path.as_ref();
+        let offset = elf::symbol_offset(&fs::read(path)?, symbol)?;
+        UprobeSpec::from_offset(path, offset)
+    }
+
+    /// Probe the file at `path` at the file offset `offset`.
+    ///
+    /// Return an error if `path` contains a NUL byte.
+    pub fn from_offset<P: AsRef<Path>>(path: P, offset: u64) -> io::Result<UprobeSpec> {
+        Ok(UprobeSpec {
+            path: CString::new(path.as_ref().as_os_str().as_bytes())?,
+            offset,
+            retprobe: false,
+        })
+    }
+
+    /// Set whether to probe function returns, rather than the probe location
+    /// itself.
+    pub fn retprobe(mut self, retprobe: bool) -> UprobeSpec {
+        self.retprobe = retprobe;
+        self
+    }
+
+    /// Return a `perf_event_attr` for this probe, using the `uprobe` PMU in
+    /// `pmus`.
+    ///
+    /// The result has `size`, `type_`, `config`, `uprobe_path` and
+    /// `probe_offset` set, and all other fields zero.
+    pub fn attr(&self, pmus: &PmuRegistry) -> Result<ProbeAttr<'_>, EventError> {
+        let mut attr = probe_attr(pmus, "uprobe", self.retprobe)?;
+        attr.set_uprobe_path(self.path.as_ptr() as u64);
+        attr.set_probe_offset(self.offset);
+        Ok(ProbeAttr {
+            attr,
+            _spec: PhantomData,
+        })
+    }
+}
+
+/// Return a `perf_event_attr` for the probe PMU `pmu_name`, with the
+/// `retprobe` format bit set if requested.
+fn probe_attr(
+    pmus: &PmuRegistry,
+    pmu_name: &str,
+    retprobe: bool,
+) -> Result<perf_event_attr, EventError> {
+    let pmu = pmus
+        .get(pmu_name)
+        .ok_or_else(|| EventError::UnknownPmu(pmu_name.to_string()))?;
+    let mut attr = perf_event_attr {
+        size: std::mem::size_of::<perf_event_attr>() as u32,
+        ..Default::default()
+    };
+    pmu.set_type(&mut attr);
+    if retprobe {
+        let unknown = || EventError::UnknownTerm {
+            pmu: pmu_name.to_string(),
+            term: "retprobe".to_string(),
+        };
+        let field = PmuFormat::load(pmu)?
+            .field("retprobe")
+            .ok_or_else(unknown)?;
+        attr.config |= field.encode(1).ok_or_else(unknown)?;
+    }
+    Ok(attr)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ffi::CStr;
+    use std::fs;
+
+    #[test]
+    fn kprobe_attrs() {
+        let root = crate::test_util::TempTree::new("kprobe");
+        root.write("kprobe/type", "6\n");
+        root.write("kprobe/format/retprobe", "config:0\n");
+        let pmus = PmuRegistry::load_from(root.path()).unwrap();
+
+        let spec = KprobeSpec::symbol("do_sys_openat2", 0x10)
+            .unwrap()
+            .retprobe(true);
+        let attr = spec.attr(&pmus).unwrap().attr;
+        assert_eq!(attr.type_, 6);
+        assert_eq!(attr.config, 1);
+        assert_eq!(attr.probe_offset(), 0x10);
+        let name = unsafe { CStr::from_ptr(attr.kprobe_func() as *const _) };
+        assert_eq!(name.to_str(), Ok("do_sys_openat2"));
+
+        let spec = KprobeSpec::address(0xffff_ffff_8100_0000);
+        let mut attr = spec.attr(&pmus).unwrap();
+        attr.set_sample_period(1);
+        attr.attr_mut().set_wakeup_events(4);
+        attr.attr_mut().set_sigtrap_flag(true);
+        let ptr = attr.as_ptr();
+        assert_eq!(unsafe { (*ptr).sample_period() }, 1);
+        assert_eq!(unsafe { (*ptr).wakeup_events() }, 4);
+        assert!(unsafe { (*ptr).sigtrap_flag() });
+        let attr = attr.attr;
+        assert_eq!(attr.config, 0);
+        assert_eq!(attr.kprobe_func(), 0);
+        assert_eq!(attr.kprobe_addr(), 0xffff_ffff_8100_0000);
+
+        assert!(KprobeSpec::symbol("bad\0name", 0).is_err());
+        assert!(matches!(
+            KprobeSpec::address(0).attr(&PmuRegistry::default()),
+            Err(EventError::UnknownPmu(_))
+        ));
+    }
+
+    #[no_mangle]
+    #[inline(never)]
+    pub extern "C" fn perf_event_open_sys_uprobe_offset_target(x: u64) -> u64 {
+        x.wrapping_mul(0x9e37_79b9_7f4a_7c15).rotate_left(17)
+    }
+
+    #[test]
+    fn uprobe_offset() {
+        let exe = std::env::current_exe().unwrap();
+        let spec = UprobeSpec::new(&exe, "perf_event_open_sys_uprobe_offset_target").unwrap();
+        assert_eq!(spec.path.as_bytes(), exe.as_os_str().as_bytes());
+
+        // The code at that offset in the file should be the code we're
+        // running.
+        let code = perf_event_open_sys_uprobe_offset_target as *const [u8; 16];
+        let file = fs::read(&exe).unwrap();
+        let offset = spec.offset as usize;
+        assert_eq!(&file[offset..offset + 16], unsafe { &*code });
+
+        assert_eq!(
+            UprobeSpec::new(&exe, "no_such_symbol_anywhere")
+                .unwrap_err()
+                .kind(),
+            io::ErrorKind::NotFound
+        );
+        assert_eq!(
+            UprobeSpec::new("/dev/null", "main").unwrap_err().kind(),
+            io::ErrorKind::InvalidData
+        );
+    }
+
+    #[no_mangle]
+    #[inline(never)]
+    pub extern "C" fn perf_event_open_sys_uprobe_live_target(x: u64) -> u64 {
+        x.wrapping_add(1)
+    }
+
+    #[test]
+    fn uprobe_live() {
+        use crate::{ioctls, read};
+        use std::os::unix::io::AsFd;
+
+        let pmus = match PmuRegistry::load() {
+            Ok(pmus) => pmus,
+            // Without sysfs there's no registry to read.
+            Err(err) if crate::test_util::unavailable(err.raw_os_error()) => return,
+            Err(err) => panic!("{}", err),
+        };
+        // Kernels built without CONFIG_UPROBE_EVENTS have no uprobe PMU.
+        if pmus.get("uprobe").is_none() {
+            return;
+        }
+        let exe = std::env::current_exe().unwrap();
+        let spec = UprobeSpec::new(&exe, "perf_event_open_sys_uprobe_live_target").unwrap();
+        let mut attr = spec.attr(&pmus).unwrap();
+        attr.set_disabled_flag(true);
+        attr.set_exclude_kernel_flag(true);
+        let fd = match unsafe { attr.open(0, -1, -1, 0) } {
+            Ok(fd) => fd,
+            // Creating uprobes requires privileges we may not have.
+            Err(err) if crate::test_util::unavailable(err.raw_os_error()) => return,
+            Err(err) => panic!("{}", err),
+        };
+
+        ioctls::enable(fd.as_fd(), ioctls::Scope::Event).unwrap();
+        for i in 0..3 {
+            std::hint::black_box(perf_event_open_sys_uprobe_live_target(
+                std::hint::black_box(i),
+            ));
+        }
+        ioctls::disable(fd.as_fd(), ioctls::Scope::Event).unwrap();
+        let value = read::read_value(fd.as_fd(), 0, 1).unwrap();
+        assert_eq!(value.values()[0].value, 3);
+    }
+}
@@ -0,0 +1,322 @@
+//! Just enough ELF parsing to find a symbol's file offset.
+//!
+//! Uprobes are placed at file offsets, but symbol tables give virtual
+//! addresses. To convert, we find the `PT_LOAD` segment containing the
+//! address and rebase it onto the segment's offset in the file.
+//!
+//! On 32-bit Arm, the low bit of a function symbol's value is set when the
+//! function is Thumb code. The instruction itself is at the even address, so
+//! we clear that bit before converting.
+
+use std::convert::TryInto;
+use std::io;
+
+const SHT_SYMTAB: u32 = 2;
+const SHT_DYNSYM: u32 = 11;
+const PT_LOAD: u32 = 1;
+const SHN_UNDEF: u16 = 0;
+const STT_FUNC: u8 = 2;
+const EM_ARM: u16 = 40;
+
+/// Return the file offset of the code or data named by `symbol` in the ELF
+/// file `data`, looking in `.symtab` first, and then `.dynsym`.
+pub(crate) fn symbol_offset(data: &[u8], symbol: &str) -> io::Result<u64> {
+    let elf = Elf::new(data)?;
+    let addr = elf.symbol_address(SHT_SYMTAB, symbol)?;
+    let addr = match addr {
+        Some(addr) => addr,
+        None => elf.symbol_address(SHT_DYNSYM, symbol)?.ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("symbol {:?} not found", symbol),
+            )
+        })?,
+    };
+    elf.address_to_offset(addr)
+}
+
+fn invalid(what: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("bad ELF file: {}", what),
+    )
+}
+
+/// An ELF file of either class and either byte order.
+struct Elf<'a> {
+    data: &'a [u8],
+    is_64: bool,
+    big_endian: bool,
+}
+
+impl<'a> Elf<'a> {
+    fn new(data: &'a [u8]) -> io::Result<Elf<'a>> {
+        if data.len() < 16 || &data[..4] != b"\x7fELF" {
+            return Err(invalid("missing ELF magic number"));
+        }
+        let is_64 = match data[4] {
+            1 => false,
+            2 => true,
+            _ => return Err(invalid("unknown class")),
+        };
+        let big_endian = match data[5] {
+            1 => false,
+            2 => true,
+            _ => return Err(invalid("unknown byte order")),
+        };
+        Ok(Elf {
+            data,
+            is_64,
+            big_endian,
+        })
+    }
+
+    fn bytes<const N: usize>(&self, offset: u64) -> io::Result<[u8; N]> {
+        let start: usize = offset.try_into().map_err(|_| invalid("offset"))?;
+        self.data
+            .get(start..)
+            .and_then(|rest| rest.get(..N))
+            .map(|bytes| bytes.try_into().unwrap())
+            .ok_or_else(|| invalid("truncated"))
+    }
+
+    fn u16(&self, offset: u64) -> io::Result<u16> {
+        let bytes = self.bytes(offset)?;
+        Ok(if self.big_endian {
+            u16::from_be_bytes(bytes)
+        } else {
+            u16::from_le_bytes(bytes)
+        })
+    }
+
+    fn u32(&self, offset: u64) -> io::Result<u32> {
+        let bytes = self.bytes(offset)?;
+        Ok(if self.big_endian {
+            u32::from_be_bytes(bytes)
+        } else {
+            u32::from_le_bytes(bytes)
+        })
+    }
+
+    fn u64(&self, offset: u64) -> io::Result<u64> {
+        let bytes = self.bytes(offset)?;
+        Ok(if self.big_endian {
+            u64::from_be_bytes(bytes)
+        } else {
+            u64::from_le_bytes(bytes)
+        })
+    }
+
+    /// Read an address-sized field: `u64` in ELF64, `u32` in ELF32.
+    fn addr(&self, offset: u64) -> io::Result<u64> {
+        if self.is_64 {
+            self.u64(offset)
+        } else {
+            self.u32(offset).map(u64::from)
+        }
+    }
+
+    /// Return the file offset, entry size, and entry count of the section
+    /// or program header table.
+    fn table(&self, sections: bool) -> io::Result<(u64, u64, u64)> {
+        let (off, entsize, num) = match (self.is_64, sections) {
+            (true, false) => (0x20, 0x36, 0x38),
+            (true, true) => (0x28, 0x3a, 0x3c),
+            (false, false) => (0x1c, 0x2a, 0x2c),
+            (false, true) => (0x20, 0x2e, 0x30),
+        };
+        Ok((
+            self.addr(off)?,
+            self.u16(entsize)? as u64,
+            self.u16(num)? as u64,
+        ))
+    }
+
+    /// Return the `(type, link, offset, size, entsize)` of section `index`.
+    fn section(&self, index: u64) -> io::Result<(u32, u32, u64, u64, u64)> {
+        let (shoff, shentsize, shnum) = self.table(true)?;
+        if index >= shnum {
+            return Err(invalid("section index"));
+        }
+        let sh = shoff + index * shentsize;
+        if self.is_64 {
+            Ok((
+                self.u32(sh + 4)?,
+                self.u32(sh + 0x28)?,
+                self.u64(sh + 0x18)?,
+                self.u64(sh + 0x20)?,
+                self.u64(sh + 0x38)?,
+            ))
+        } else {
+            Ok((
+                self.u32(sh + 4)?,
+                self.u32(sh + 0x18)?,
+                self.u32(sh + 0x10)? as u64,
+                self.u32(sh + 0x14)? as u64,
+                self.u32(sh + 0x24)? as u64,
+            ))
+        }
+    }
+
+    /// Return the NUL-terminated string at `offset` in the string table
+    /// section `strtab`.
+    fn string(&self, strtab: u64, offset: u64) -> io::Result<&'a [u8]> {
+        let (_, _, start, size, _) = self.section(strtab)?;
+        if offset >= size {
+            return Err(invalid("string offset"));
+        }
+        let start: usize = (start + offset).try_into().map_err(|_| invalid("offset"))?;
+        let rest = self.data.get(start..).ok_or_else(|| invalid("truncated"))?;
+        let len = rest
+            .iter()
+            .position(|&b| b == 0)
+            .ok_or_else(|| invalid("unterminated string"))?;
+        Ok(&rest[..len])
+    }
+
+    /// Look up the defined symbol `name` in the symbol table sections of
+    /// type `sh_type`, returning its address.
+    fn symbol_address(&self, sh_type: u32, name: &str) -> io::Result<Option<u64>> {
+        let (_, _, shnum) = self.table(true)?;
+        for index in 0..shnum {
+            let (type_, link, offset, size, entsize) = self.section(index)?;
+            if type_ != sh_type || entsize == 0 {
+                continue;
+            }
+            for sym in (0..size / entsize).map(|i| offset + i * entsize) {
+                let (value, info, shndx) = if self.is_64 {
+                    (
+                        self.u64(sym + 8)?,
+                        self.bytes::<1>(sym + 4)?[0],
+                        self.u16(sym + 6)?,
+                    )
+                } else {
+                    (
+                        self.u32(sym + 4)? as u64,
+                        self.bytes::<1>(sym + 12)?[0],
+                        self.u16(sym + 14)?,
+                    )
+                };
+                if shndx == SHN_UNDEF || value == 0 {
+                    continue;
+                }
+                if self.string(link as u64, self.u32(sym)? as u64)? == name.as_bytes() {
+                    if info & 0xf == STT_FUNC && self.u16(0x12)? == EM_ARM {
+                        return Ok(Some(value & !1));
+                    }
+                    return Ok(Some(value));
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    /// Convert the virtual address `addr` to a file offset, using the
+    /// `PT_LOAD` segment that contains it.
+    fn address_to_offset(&self, addr: u64) -> io::Result<u64> {
+        let (phoff, phentsize, phnum) = self.table(false)?;
+        for ph in (0..phnum).map(|i| phoff + i * phentsize) {
+            if self.u32(ph)? != PT_LOAD {
+                continue;
+            }
+            let (offset, vaddr, filesz) = if self.is_64 {
+                (
+                    self.u64(ph + 8)?,
+                    self.u64(ph + 0x10)?,
+                    self.u64(ph + 0x20)?,
+                )
+            } else {
+                (
+                    self.u32(ph + 4)? as u64,
+                    self.u32(ph + 8)? as u64,
+                    self.u32(ph + 0x10)? as u64,
+                )
+            };
+            if vaddr <= addr && addr - vaddr < filesz {
+                return Ok(addr - vaddr + offset);
+            }
+        }
+        Err(invalid("symbol address is not in any loadable segment"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Build a little-endian ELF32 file for `machine`, with one `PT_LOAD`
+    /// segment mapping file offset 0 at 0x10000, and the symbols
+    /// `(name, value, st_info)`.
+    fn elf32(machine: u16, symbols: &[(&str, u32, u8)]) -> Vec<u8> {
+        let mut strtab = vec![0];
+        let mut symtab = vec![0; 16];
+        for &(name, value, info) in symbols {
+            symtab.extend_from_slice(&(strtab.len() as u32).to_le_bytes());
+            symtab.extend_from_slice(&value.to_le_bytes());
+            symtab.extend_from_slice(&0_u32.to_le_bytes());
+            symtab.extend_from_slice(&[info, 0]);
+            symtab.extend_from_slice(&1_u16.to_le_bytes());
+            strtab.extend_from_slice(name.as_bytes());
+            strtab.push(0);
+        }
+
+        let strtab_off = 52 + 32;
+        let symtab_off = strtab_off + strtab.len() as u32;
+        let shoff = symtab_off + symtab.len() as u32;
+        let mut data = vec![0; 52];
+        data[..7].copy_from_slice(b"\x7fELF\x01\x01\x01");
+        data[0x12..0x14].copy_from_slice(&machine.to_le_bytes());
+        data[0x1c..0x20].copy_from_slice(&52_u32.to_le_bytes());
+        data[0x20..0x24].copy_from_slice(&shoff.to_le_bytes());
+        data[0x2a..0x2c].copy_from_slice(&32_u16.to_le_bytes());
+        data[0x2c..0x2e].copy_from_slice(&1_u16.to_le_bytes());
+        data[0x2e..0x30].copy_from_slice(&40_u16.to_le_bytes());
+        data[0x30..0x32].copy_from_slice(&3_u16.to_le_bytes());
+
+        // p_type, p_offset, p_vaddr, p_paddr, p_filesz, p_memsz, p_flags, p_align
+        for word in [PT_LOAD, 0, 0x10000, 0x10000, 0x1000, 0x1000, 5, 0x1000] {
+            data.extend_from_slice(&word.to_le_bytes());
+        }
+        data.extend_from_slice(&strtab);
+        data.extend_from_slice(&symtab);
+
+        // The null section, then .symtab, linked to .strtab.
+        let sections = [
+            [0; 10],
+            [
+                0,
+                SHT_SYMTAB,
+                0,
+                0,
+                symtab_off,
+                symtab.len() as u32,
+                2,
+                1,
+                4,
+                16,
+            ],
+            [0, 3, 0, 0, strtab_off, strtab.len() as u32, 0, 0, 1, 0],
+        ];
+        for word in sections.iter().flatten() {
+            data.extend_from_slice(&word.to_le_bytes());
+        }
+        data
+    }
+
+    #[test]
+    fn arm_thumb_functions() {
+        let symbols = [
+            ("thumb_fn", 0x10101, 0x12),
+            ("arm_fn", 0x10200, 0x12),
+            ("odd_object", 0x10301, 0x11),
+        ];
+        let arm = elf32(EM_ARM, &symbols);
+        assert_eq!(symbol_offset(&arm, "thumb_fn").unwrap(), 0x100);
+        assert_eq!(symbol_offset(&arm, "arm_fn").unwrap(), 0x200);
+        assert_eq!(symbol_offset(&arm, "odd_object").unwrap(), 0x301);
+
+        // Other architectures have no Thumb bit.
+        let i386 = elf32(3, &symbols);
+        assert_eq!(symbol_offset(&i386, "thumb_fn").unwrap(), 0x101);
+    }
+}
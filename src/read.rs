@@ -0,0 +1,321 @@
+//! Decoding counter values read from a perf event.
+//!
+//! Calling `read(2)` on a perf event file descriptor returns its counter
+//! value, laid out according to the `read_format` field of the
+//! `perf_event_attr` that opened it:
+//!
+//! ```text
+//! struct read_format {                    struct read_format {
+//!     u64 value;                              u64 nr;
+//!     u64 time_enabled;  // if ENABLED        u64 time_enabled;  // if ENABLED
+//!     u64 time_running;  // if RUNNING        u64 time_running;  // if RUNNING
+//!     u64 id;            // if ID             struct {
+//!     u64 lost;          // if LOST               u64 value;
+//! };                                              u64 id;        // if ID
+//!                                                 u64 lost;      // if LOST
+//!                                             } values[nr];
+//!                                         };  // if PERF_FORMAT_GROUP
+//! ```
+//!
+//! The same layout appears in samples with `PERF_SAMPLE_READ` and in
+//! `PERF_RECORD_READ` records. The [`parse`] function decodes it into a
+//! [`ReadValue`], and [`read_size`] says how large a buffer `read(2)` needs.
+//!
+//! When more events are enabled than the PMU has counters, the kernel
+//! multiplexes them, and each counts only part of the time. The
+//! [`ReadValue::scale`] method extrapolates a value to the full enabled time.
+
+use crate::bindings;
+use crate::record::{Cursor, ParseError};
+use std::io;
+use std::os::unix::io::{AsRawFd, BorrowedFd};
+
+/// `PERF_FORMAT_LOST`: include the number of lost samples for each event.
+///
+/// This was added in Linux 6.0, which is newer than the headers the
+/// bindings were generated from.
+pub const PERF_FORMAT_LOST: u64 = 1 << 4;
+
+/// Counter values read from a perf event.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ReadValue {
+    /// The value of a single event, without `PERF_FORMAT_GROUP`.
+    Single {
+        /// The time the event was enabled, with
+        /// `PERF_FORMAT_TOTAL_TIME_ENABLED`.
+        time_enabled: Option<u64>,
+
+        /// The time the event was actually counting, with
+        /// `PERF_FORMAT_TOTAL_TIME_RUNNING`.
+        time_running: Option<u64>,
+
+        value: CounterValue,
+    },
+
+    /// The values of all the events in a group, with `PERF_FORMAT_GROUP`.
+    Group {
+        /// The time the group was enabled, with
+        /// `PERF_FORMAT_TOTAL_TIME_ENABLED`.
+        time_enabled: Option<u64>,
+
+        /// The time the group was actually counting, with
+        /// `PERF_FORMAT_TOTAL_TIME_RUNNING`.
+        time_running: Option<u64>,
+
+        /// The group members' values, leader first.
+        values: Vec<CounterValue>,
+    },
+}
+
+/// The value of a single counter.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CounterValue {
+    pub value: u64,
+
+    /// The event's id, with `PERF_FORMAT_ID`.
+    pub id: Option<u64>,
+
+    /// The number of lost samples, with [`PERF_FORMAT_LOST`].
+    pub lost: Option<u64>,
+}
+
+impl ReadValue {
+    /// Return the time the event or group was enabled, if requested.
+    pub fn time_enabled(&self) -> Option<u64> {
+        match *self {
+            ReadValue::Single { time_enabled, .. } | ReadValue::Group { time_enabled, .. } => {
+                time_enabled
+            }
+        }
+    }
+
+    /// Return the time the event or group was counting, if requested.
+    pub fn time_running(&self) -> Option<u64> {
+        match *self {
+            ReadValue::Single { time_running, .. } | ReadValue::Group { time_running, .. } => {
+                time_running
+            }
+        }
+    }
+
+    /// Return the counter values: one for a single event, or one per member
+    /// for a group.
+    pub fn values(&self) -> &[CounterValue] {
+        match self {
+            ReadValue::Single { value, .. } => std::slice::from_ref(value),
+            ReadValue::Group { values, .. } => values,
+        }
+    }
+
+    /// Scale `value` to account for multiplexing, by multiplying it by
+    /// `time_enabled / time_running`.
+    ///
+    /// If either time was not requested in `read_format`, return `value`
+    /// unchanged. If the event was enabled but never ran, return `None`: there
+    /// is no basis for an estimate.
+    pub fn scale(&self, value: u64) -> Option<u64> {
+        match (self.time_enabled(), self.time_running()) {
+            (Some(enabled), Some(running)) => {
+                if running == 0 {
+                    return if enabled == 0 { Some(value) } else { None };
+                }
+                let scaled = value as u128 * enabled as u128 / running as u128;
+                Some(scaled.min(u64::MAX as u128) as u64)
+            }
+            _ => Some(value),
+        }
+    }
+}
+
+/// Return the number of bytes `read(2)` returns for an event with the given
+/// `read_format`.
+///
+/// If `read_format` includes `PERF_FORMAT_GROUP`, `members` is the number of
+/// events in the group, including the leader. Otherwise, it is ignored.
+pub fn read_size(read_format: u64, members: usize) -> usize {
+    let format = Format::new(read_format);
+    let times = format.enabled as usize + format.running as usize;
+    let per_value = 1 + format.id as usize + format.lost as usize;
+
+    if format.group {
+        8 * (1 + times + members * per_value)
+    } else {
+        8 * (times + per_value)
+    }
+}
+
+/// Decode counter values laid out as described by `read_format`.
+///
+/// Any bytes following the values are ignored.
+pub fn parse(read_format: u64, bytes: &[u8]) -> Result<ReadValue, ParseError> {
+    parse_cursor(read_format, &mut Cursor::new(bytes))
+}
+
+pub(crate) fn parse_cursor(read_format: u64, c: &mut Cursor) -> Result<ReadValue, ParseError> {
+    let format = Format::new(read_format);
+    let value = |c: &mut Cursor| -> Result<CounterValue, ParseError> {
+        Ok(CounterValue {
+            value: c.u64()?,
+            id: if format.id { Some(c.u64()?) } else { None },
+            lost: if format.lost { Some(c.u64()?) } else { None },
+        })
+    };
+    let optional = |c: &mut Cursor, present: bool| -> Result<Option<u64>, ParseError> {
+        if present {
+            Ok(Some(c.u64()?))
+        } else {
+            Ok(None)
+        }
+    };
+
+    if format.group {
+        let nr = c.u64()?;
+        let time_enabled = optional(c, format.enabled)?;
+        let time_running = optional(c, format.running)?;
+        if nr > c.len() as u64 / 8 {
+            return Err(ParseError::Truncated);
+        }
+        let values = (0..nr)
+            .map(|_| value(c))
+            .collect::<Result<_, ParseError>>()?;
+        Ok(ReadValue::Group {
+            time_enabled,
+            time_running,
+            values,
+        })
+    } else {
+        // For a single event, the value comes before the times, but the id
+        // and lost count come after.
+        let count = c.u64()?;
+        let time_enabled = optional(c, format.enabled)?;
+        let time_running = optional(c, format.running)?;
+        Ok(ReadValue::Single {
+            time_enabled,
+            time_running,
+            value: CounterValue {
+                value: count,
+                id: optional(c, format.id)?,
+                lost: optional(c, format.lost)?,
+            },
+        })
+    }
+}
+
+/// Read the counter values of the perf event `fd`, which was opened with the
+/// given `read_format`.
+///
+/// If `read_format` includes `PERF_FORMAT_GROUP`, `fd` must be a group leader,
+/// and `members` the number of events in its group, including itself.
+pub fn read_value(fd: BorrowedFd<'_>, read_format: u64, members: usize) -> io::Result<ReadValue> {
+    let mut buf = vec![0_u8; read_size(read_format, members)];
+    let len = unsafe { libc::read(fd.as_raw_fd(), buf.as_mut_ptr() as *mut _, buf.len()) };
+    if len < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    parse(read_format, &buf[..len as usize])
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+}
+
+/// The `read_format` bits, decoded.
+struct Format {
+    enabled: bool,
+    running: bool,
+    id: bool,
+    group: bool,
+    lost: bool,
+}
+
+impl Format {
+    fn new(read_format: u64) -> Format {
+        use bindings::*;
+
+        let has = |bit: u32| read_format & bit as u64 != 0;
+        Format {
+            enabled: has(perf_event_read_format_PERF_FORMAT_TOTAL_TIME_ENABLED),
+            running: has(perf_event_read_format_PERF_FORMAT_TOTAL_TIME_RUNNING),
+            id: has(perf_event_read_format_PERF_FORMAT_ID),
+            group: has(perf_event_read_format_PERF_FORMAT_GROUP),
+            lost: read_format & PERF_FORMAT_LOST != 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bindings::*;
+
+    const ENABLED: u64 = perf_event_read_format_PERF_FORMAT_TOTAL_TIME_ENABLED as u64;
+    const RUNNING: u64 = perf_event_read_format_PERF_FORMAT_TOTAL_TIME_RUNNING as u64;
+    const ID: u64 = perf_event_read_format_PERF_FORMAT_ID as u64;
+    const GROUP: u64 = perf_event_read_format_PERF_FORMAT_GROUP as u64;
+
+    fn bytes(values: &[u64]) -> Vec<u8> {
+        values.iter().flat_map(|v| v.to_ne_bytes()).collect()
+    }
+
+    #[test]
+    fn every_combination() {
+        for read_format in 0..32 {
+            let members = 3;
+            let size = read_size(read_format, members);
+            let mut words: Vec<u64> = (1..=size as u64 / 8).collect();
+            if read_format & GROUP != 0 {
+                words[0] = members as u64;
+            }
+            let value = parse(read_format, &bytes(&words)).unwrap();
+
+            assert_eq!(value.time_enabled().is_some(), read_format & ENABLED != 0);
+            assert_eq!(value.time_running().is_some(), read_format & RUNNING != 0);
+            let expected_members = if read_format & GROUP != 0 { members } else { 1 };
+            assert_eq!(value.values().len(), expected_members);
+            for member in value.values() {
+                assert_eq!(member.id.is_some(), read_format & ID != 0);
+                assert_eq!(member.lost.is_some(), read_format & PERF_FORMAT_LOST != 0);
+            }
+
+            // One byte short must fail.
+            let short = bytes(&words);
+            assert_eq!(
+                parse(read_format, &short[..short.len() - 1]),
+                Err(ParseError::Truncated),
+                "read_format {:#x}",
+                read_format
+            );
+        }
+    }
+
+    #[test]
+    fn single_layout() {
+        let value = parse(ENABLED | RUNNING | ID, &bytes(&[100, 2000, 1000, 7])).unwrap();
+        assert_eq!(
+            value,
+            ReadValue::Single {
+                time_enabled: Some(2000),
+                time_running: Some(1000),
+                value: CounterValue {
+                    value: 100,
+                    id: Some(7),
+                    lost: None
+                }
+            }
+        );
+        assert_eq!(value.scale(100), Some(200));
+    }
+
+    #[test]
+    fn group_layout() {
+        let value = parse(GROUP | ID | RUNNING, &bytes(&[2, 50, 10, 1, 20, 2])).unwrap();
+        assert_eq!(value.time_running(), Some(50));
+        assert_eq!(value.values()[1].value, 20);
+        assert_eq!(value.values()[1].id, Some(2));
+        // Without both times, no scaling is possible.
+        assert_eq!(value.scale(20), Some(20));
+    }
+
+    #[test]
+    fn never_ran() {
+        let value = parse(ENABLED | RUNNING, &bytes(&[0, 500, 0])).unwrap();
+        assert_eq!(value.scale(0), None);
+    }
+}
@@ -0,0 +1,814 @@
+//! Decoding the records a perf event writes to its ring buffer.
+//!
+//! Each record in a perf event's ring buffer starts with a
+//! [`perf_event_header`] whose `type_` field is one of the
+//! `perf_event_type_PERF_RECORD_*` constants. The layout of the body that
+//! follows is described only in comments in `<linux/perf_event.h>`; the
+//! [`Record::parse`] function decodes it into a [`Record`].
+//!
+//! If the event's `sample_id_all` flag is set, non-sample records carry a
+//! trailer identifying the task, time and CPU; use a [`RecordParser`] to
+//! decode those.
+//!
+//! Record types this module doesn't know about are returned as
+//! [`Record::Unknown`], so newer kernels never cause parsing to fail.
+//!
+//! Strings in records, like file names and command names, are returned as
+//! byte slices without their terminating NUL and padding, since Linux does
+//! not require them to be UTF-8.
+//!
+//! The bodies of `PERF_RECORD_SAMPLE` and `PERF_RECORD_READ` records depend on
+//! the `sample_type` and `read_format` fields of the `perf_event_attr` that
+//! opened the event, so this module leaves them undecoded.
+//!
+//! [`perf_event_header`]: crate::bindings::perf_event_header
+
+use crate::bindings::{self, perf_event_attr, perf_event_header};
+use std::fmt;
+
+/// A decoded record from a perf event's ring buffer.
+///
+/// Process and thread ids are as the kernel reports them: `u32` values that
+/// may be `u32::MAX` (that is, `-1`) when not applicable.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Record<'a> {
+    /// `PERF_RECORD_MMAP`: an executable mapping was created.
+    Mmap {
+        pid: u32,
+        tid: u32,
+        addr: u64,
+        len: u64,
+        pgoff: u64,
+        filename: &'a [u8],
+    },
+
+    /// `PERF_RECORD_LOST`: records were lost because the ring buffer was full.
+    Lost { id: u64, lost: u64 },
+
+    /// `PERF_RECORD_COMM`: a process changed its name.
+    Comm {
+        pid: u32,
+        tid: u32,
+        comm: &'a [u8],
+
+        /// True if the name changed because the process called `exec`.
+        exec: bool,
+    },
+
+    /// `PERF_RECORD_EXIT`: a process exited.
+    Exit {
+        pid: u32,
+        ppid: u32,
+        tid: u32,
+        ptid: u32,
+        time: u64,
+    },
+
+    /// `PERF_RECORD_THROTTLE`: the kernel throttled the event's interrupts.
+    Throttle { time: u64, id: u64, stream_id: u64 },
+
+    /// `PERF_RECORD_UNTHROTTLE`: the kernel stopped throttling the event.
+    Unthrottle { time: u64, id: u64, stream_id: u64 },
+
+    /// `PERF_RECORD_FORK`: a process forked.
+    Fork {
+        pid: u32,
+        ppid: u32,
+        tid: u32,
+        ptid: u32,
+        time: u64,
+    },
+
+    /// `PERF_RECORD_READ`: a counter value, for inherited events with
+    /// `inherit_stat` set.
+    ///
+    /// The layout of `values` is determined by the event's `read_format`;
+    /// use [`read::parse`] to decode it.
+    ///
+    /// [`read::parse`]: crate::read::parse
+    Read {
+        pid: u32,
+        tid: u32,
+        values: &'a [u8],
+    },
+
+    /// `PERF_RECORD_SAMPLE`: a sample.
+    ///
+    /// The layout of the body is determined by the event's `sample_type`,
+    /// `read_format` and other attributes.
+    Sample(&'a [u8]),
+
+    /// `PERF_RECORD_MMAP2`: a mapping was created, with more detail than
+    /// `PERF_RECORD_MMAP`.
+    Mmap2 {
+        pid: u32,
+        tid: u32,
+        addr: u64,
+        len: u64,
+        pgoff: u64,
+        file: Mmap2File<'a>,
+        prot: u32,
+        flags: u32,
+        filename: &'a [u8],
+    },
+
+    /// `PERF_RECORD_AUX`: new data is available in the AUX area.
+    Aux {
+        aux_offset: u64,
+        aux_size: u64,
+        flags: u64,
+    },
+
+    /// `PERF_RECORD_ITRACE_START`: instruction tracing started for a task.
+    ItraceStart { pid: u32, tid: u32 },
+
+    /// `PERF_RECORD_LOST_SAMPLES`: samples were lost in the PMU hardware.
+    LostSamples { lost: u64 },
+
+    /// `PERF_RECORD_SWITCH`: a context switch into or out of the monitored
+    /// task.
+    Switch {
+        /// True for a switch out of the task, false for a switch in.
+        out: bool,
+
+        /// True if a switch out was a preemption.
+        preempt: bool,
+    },
+
+    /// `PERF_RECORD_SWITCH_CPU_WIDE`: a context switch on a CPU being
+    /// monitored system-wide.
+    SwitchCpuWide {
+        /// True for a switch out of the task, false for a switch in.
+        out: bool,
+
+        /// True if a switch out was a preemption.
+        preempt: bool,
+
+        /// The process switched to (on a switch out) or from (on a switch
+        /// in).
+        next_prev_pid: u32,
+        next_prev_tid: u32,
+    },
+
+    /// `PERF_RECORD_NAMESPACES`: a task's namespaces, on creation or change.
+    Namespaces {
+        pid: u32,
+        tid: u32,
+        namespaces: Vec<NamespaceLink>,
+    },
+
+    /// `PERF_RECORD_KSYMBOL`: a kernel symbol was registered or unregistered.
+    Ksymbol {
+        addr: u64,
+        len: u32,
+
+        /// One of the `perf_record_ksymbol_type_*` constants.
+        ksym_type: u16,
+        flags: u16,
+        name: &'a [u8],
+    },
+
+    /// `PERF_RECORD_BPF_EVENT`: a BPF program was loaded or unloaded.
+    BpfEvent {
+        /// One of the `perf_bpf_event_type_*` constants.
+        type_: u16,
+        flags: u16,
+        id: u32,
+        tag: [u8; BPF_TAG_SIZE],
+    },
+
+    /// `PERF_RECORD_CGROUP`: a cgroup was created.
+    Cgroup { id: u64, path: &'a [u8] },
+
+    /// `PERF_RECORD_TEXT_POKE`: kernel text was modified.
+    TextPoke {
+        addr: u64,
+        old_bytes: &'a [u8],
+        new_bytes: &'a [u8],
+    },
+
+    /// `PERF_RECORD_AUX_OUTPUT_HW_ID`: the hardware id of an AUX output
+    /// event.
+    AuxOutputHwId { hw_id: u64 },
+
+    /// A record of a type this module doesn't recognize.
+    Unknown {
+        type_: u32,
+        misc: u16,
+        body: &'a [u8],
+    },
+}
+
+/// How a `PERF_RECORD_MMAP2` record identifies the mapped file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Mmap2File<'a> {
+    /// The file's device and inode.
+    Inode {
+        maj: u32,
+        min: u32,
+        ino: u64,
+        ino_generation: u64,
+    },
+
+    /// The file's build id, when the event's `build_id` flag is set.
+    BuildId(&'a [u8]),
+}
+
+/// A namespace, as reported by `PERF_RECORD_NAMESPACES`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct NamespaceLink {
+    pub dev: u64,
+    pub inode: u64,
+}
+
+/// The length of a BPF program tag, `BPF_TAG_SIZE` in `<linux/bpf.h>`.
+pub const BPF_TAG_SIZE: usize = 8;
+
+/// The maximum length of a build id in a `PERF_RECORD_MMAP2` record.
+const BUILD_ID_MAX: usize = 20;
+
+impl<'a> Record<'a> {
+    /// Decode a record with the given `header` and `body`.
+    ///
+    /// The `body` should be the bytes following the header, as returned by
+    /// [`RawRecord::body`], for example.
+    ///
+    /// Any bytes following the fields of the given record type, like a
+    /// `sample_id` trailer, are ignored. Use [`RecordParser`] to decode the
+    /// trailer as well.
+    ///
+    /// [`RawRecord::body`]: crate::ring_buffer::RawRecord::body
+    pub fn parse(header: &perf_event_header, body: &'a [u8]) -> Result<Record<'a>, ParseError> {
+        let mut c = Cursor::new(body);
+        let misc = header.misc as u32;
+
+        let record = match header.type_ {
+            bindings::perf_event_type_PERF_RECORD_MMAP => Record::Mmap {
+                pid: c.u32()?,
+                tid: c.u32()?,
+                addr: c.u64()?,
+                len: c.u64()?,
+                pgoff: c.u64()?,
+                filename: c.string()?,
+            },
+            bindings::perf_event_type_PERF_RECORD_LOST => Record::Lost {
+                id: c.u64()?,
+                lost: c.u64()?,
+            },
+            bindings::perf_event_type_PERF_RECORD_COMM => Record::Comm {
+                pid: c.u32()?,
+                tid: c.u32()?,
+                comm: c.string()?,
+                exec: misc & bindings::PERF_RECORD_MISC_COMM_EXEC != 0,
+            },
+            bindings::perf_event_type_PERF_RECORD_EXIT => Record::Exit {
+                pid: c.u32()?,
+                ppid: c.u32()?,
+                tid: c.u32()?,
+                ptid: c.u32()?,
+                time: c.u64()?,
+            },
+            bindings::perf_event_type_PERF_RECORD_THROTTLE => Record::Throttle {
+                time: c.u64()?,
+                id: c.u64()?,
+                stream_id: c.u64()?,
+            },
+            bindings::perf_event_type_PERF_RECORD_UNTHROTTLE => Record::Unthrottle {
+                time: c.u64()?,
+                id: c.u64()?,
+                stream_id: c.u64()?,
+            },
+            bindings::perf_event_type_PERF_RECORD_FORK => Record::Fork {
+                pid: c.u32()?,
+                ppid: c.u32()?,
+                tid: c.u32()?,
+                ptid: c.u32()?,
+                time: c.u64()?,
+            },
+            bindings::perf_event_type_PERF_RECORD_READ => Record::Read {
+                pid: c.u32()?,
+                tid: c.u32()?,
+                values: c.rest(),
+            },
+            bindings::perf_event_type_PERF_RECORD_SAMPLE => Record::Sample(c.rest()),
+            bindings::perf_event_type_PERF_RECORD_MMAP2 => Record::Mmap2 {
+                pid: c.u32()?,
+                tid: c.u32()?,
+                addr: c.u64()?,
+                len: c.u64()?,
+                pgoff: c.u64()?,
+                file: if misc & bindings::PERF_RECORD_MISC_MMAP_BUILD_ID != 0 {
+                    let size = c.u8()? as usize;
+                    c.skip(3)?;
+                    let build_id = c.bytes(BUILD_ID_MAX)?;
+                    if size > BUILD_ID_MAX {
+                        return Err(ParseError::Invalid("build id size too large"));
+                    }
+                    Mmap2File::BuildId(&build_id[..size])
+                } else {
+                    Mmap2File::Inode {
+                        maj: c.u32()?,
+                        min: c.u32()?,
+                        ino: c.u64()?,
+                        ino_generation: c.u64()?,
+                    }
+                },
+                prot: c.u32()?,
+                flags: c.u32()?,
+                filename: c.string()?,
+            },
+            bindings::perf_event_type_PERF_RECORD_AUX => Record::Aux {
+                aux_offset: c.u64()?,
+                aux_size: c.u64()?,
+                flags: c.u64()?,
+            },
+            bindings::perf_event_type_PERF_RECORD_ITRACE_START => Record::ItraceStart {
+                pid: c.u32()?,
+                tid: c.u32()?,
+            },
+            bindings::perf_event_type_PERF_RECORD_LOST_SAMPLES => {
+                Record::LostSamples { lost: c.u64()? }
+            }
+            bindings::perf_event_type_PERF_RECORD_SWITCH => Record::Switch {
+                out: misc & bindings::PERF_RECORD_MISC_SWITCH_OUT != 0,
+                preempt: misc & bindings::PERF_RECORD_MISC_SWITCH_OUT_PREEMPT != 0,
+            },
+            bindings::perf_event_type_PERF_RECORD_SWITCH_CPU_WIDE => Record::SwitchCpuWide {
+                out: misc & bindings::PERF_RECORD_MISC_SWITCH_OUT != 0,
+                preempt: misc & bindings::PERF_RECORD_MISC_SWITCH_OUT_PREEMPT != 0,
+                next_prev_pid: c.u32()?,
+                next_prev_tid: c.u32()?,
+            },
+            bindings::perf_event_type_PERF_RECORD_NAMESPACES => {
+                let pid = c.u32()?;
+                let tid = c.u32()?;
+                let nr = c.u64()?;
+                if nr > c.len() as u64 / 16 {
+                    return Err(ParseError::Truncated);
+                }
+                let namespaces = (0..nr)
+                    .map(|_| {
+                        Ok(NamespaceLink {
+                            dev: c.u64()?,
+                            inode: c.u64()?,
+                        })
+                    })
+                    .collect::<Result<_, ParseError>>()?;
+                Record::Namespaces {
+                    pid,
+                    tid,
+                    namespaces,
+                }
+            }
+            bindings::perf_event_type_PERF_RECORD_KSYMBOL => Record::Ksymbol {
+                addr: c.u64()?,
+                len: c.u32()?,
+                ksym_type: c.u16()?,
+                flags: c.u16()?,
+                name: c.string()?,
+            },
+            bindings::perf_event_type_PERF_RECORD_BPF_EVENT => Record::BpfEvent {
+                type_: c.u16()?,
+                flags: c.u16()?,
+                id: c.u32()?,
+                tag: c.array()?,
+            },
+            bindings::perf_event_type_PERF_RECORD_CGROUP => Record::Cgroup {
+                id: c.u64()?,
+                path: c.string()?,
+            },
+            bindings::perf_event_type_PERF_RECORD_TEXT_POKE => {
+                let addr = c.u64()?;
+                let old_len = c.u16()? as usize;
+                let new_len = c.u16()? as usize;
+                Record::TextPoke {
+                    addr,
+                    old_bytes: c.bytes(old_len)?,
+                    new_bytes: c.bytes(new_len)?,
+                }
+            }
+            bindings::perf_event_type_PERF_RECORD_AUX_OUTPUT_HW_ID => {
+                Record::AuxOutputHwId { hw_id: c.u64()? }
+            }
+            type_ => Record::Unknown {
+                type_,
+                misc: header.misc,
+                body,
+            },
+        };
+
+        Ok(record)
+    }
+}
+
+/// A decoder for records that understands the `sample_id` trailer.
+///
+/// When an event's `sample_id_all` flag is set, the kernel appends a
+/// `sample_id` trailer to every record other than `PERF_RECORD_SAMPLE`. The
+/// trailer's contents are selected by the event's `sample_type`, drawing on
+/// the `PERF_SAMPLE_TID`, `_TIME`, `_ID`, `_STREAM_ID`, `_CPU` and
+/// `_IDENTIFIER` bits. A `RecordParser` built from the `perf_event_attr` that
+/// opened the event strips and decodes the trailer, so that records from
+/// several ring buffers can be merged by timestamp.
+#[derive(Clone, Debug)]
+pub struct RecordParser {
+    /// The event's `sample_type`, if `sample_id_all` is set.
+    sample_id_type: Option<u64>,
+}
+
+/// The `sample_id` trailer of a non-sample record.
+///
+/// Each field is `Some` if and only if the corresponding bit was set in the
+/// event's `sample_type`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct SampleId {
+    /// `PERF_SAMPLE_TID`: the process id.
+    pub pid: Option<u32>,
+
+    /// `PERF_SAMPLE_TID`: the thread id.
+    pub tid: Option<u32>,
+
+    /// `PERF_SAMPLE_TIME`: the timestamp.
+    pub time: Option<u64>,
+
+    /// `PERF_SAMPLE_ID` or `PERF_SAMPLE_IDENTIFIER`: the id of the event
+    /// that produced the record.
+    pub id: Option<u64>,
+
+    /// `PERF_SAMPLE_STREAM_ID`: the id of the event that produced the
+    /// record, or of its parent if it was inherited.
+    pub stream_id: Option<u64>,
+
+    /// `PERF_SAMPLE_CPU`: the CPU on which the record was produced.
+    pub cpu: Option<u32>,
+}
+
+impl RecordParser {
+    /// Return a parser for records from an event opened with `attrs`.
+    pub fn new(attrs: &perf_event_attr) -> RecordParser {
+        RecordParser {
+            sample_id_type: if attrs.sample_id_all_flag() {
+                Some(attrs.sample_type)
+            } else {
+                None
+            },
+        }
+    }
+
+    /// Return the size of the `sample_id` trailer on non-sample records, in
+    /// bytes. This is zero if `sample_id_all` is not set.
+    pub fn sample_id_size(&self) -> usize {
+        use bindings::*;
+
+        let sample_type = match self.sample_id_type {
+            Some(sample_type) => sample_type,
+            None => return 0,
+        };
+        [
+            perf_event_sample_format_PERF_SAMPLE_TID,
+            perf_event_sample_format_PERF_SAMPLE_TIME,
+            perf_event_sample_format_PERF_SAMPLE_ID,
+            perf_event_sample_format_PERF_SAMPLE_STREAM_ID,
+            perf_event_sample_format_PERF_SAMPLE_CPU,
+            perf_event_sample_format_PERF_SAMPLE_IDENTIFIER,
+        ]
+        .iter()
+        .filter(|&&bit| sample_type & bit != 0)
+        .count()
+            * 8
+    }
+
+    /// Decode a record with the given `header` and `body`, along with its
+    /// `sample_id` trailer, if it has one.
+    ///
+    /// The trailer is `None` for `PERF_RECORD_SAMPLE` records, and for all
+    /// records if the event's `sample_id_all` flag is not set.
+    pub fn parse<'a>(
+        &self,
+        header: &perf_event_header,
+        body: &'a [u8],
+    ) -> Result<(Record<'a>, Option<SampleId>), ParseError> {
+        let sample_type = match self.sample_id_type {
+            Some(sample_type) if header.type_ != bindings::perf_event_type_PERF_RECORD_SAMPLE => {
+                sample_type
+            }
+            _ => return Ok((Record::parse(header, body)?, None)),
+        };
+
+        let size = self.sample_id_size();
+        if size > body.len() {
+            return Err(ParseError::Truncated);
+        }
+        let (body, trailer) = body.split_at(body.len() - size);
+        let sample_id = parse_sample_id(sample_type, trailer)?;
+        Ok((Record::parse(header, body)?, Some(sample_id)))
+    }
+}
+
+fn parse_sample_id(sample_type: u64, trailer: &[u8]) -> Result<SampleId, ParseError> {
+    use bindings::*;
+
+    let mut c = Cursor::new(trailer);
+    let mut sample_id = SampleId::default();
+    let has = |bit: u64| sample_type & bit != 0;
+
+    if has(perf_event_sample_format_PERF_SAMPLE_TID) {
+        sample_id.pid = Some(c.u32()?);
+        sample_id.tid = Some(c.u32()?);
+    }
+    if has(perf_event_sample_format_PERF_SAMPLE_TIME) {
+        sample_id.time = Some(c.u64()?);
+    }
+    if has(perf_event_sample_format_PERF_SAMPLE_ID) {
+        sample_id.id = Some(c.u64()?);
+    }
+    if has(perf_event_sample_format_PERF_SAMPLE_STREAM_ID) {
+        sample_id.stream_id = Some(c.u64()?);
+    }
+    if has(perf_event_sample_format_PERF_SAMPLE_CPU) {
+        sample_id.cpu = Some(c.u32()?);
+        c.skip(4)?;
+    }
+    if has(perf_event_sample_format_PERF_SAMPLE_IDENTIFIER) {
+        sample_id.id = Some(c.u64()?);
+    }
+
+    Ok(sample_id)
+}
+
+/// An error decoding a record.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ParseError {
+    /// The record was too short to hold the fields its type requires.
+    Truncated,
+
+    /// A field held a value that is not permitted.
+    Invalid(&'static str),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseError::Truncated => f.write_str("perf record truncated"),
+            ParseError::Invalid(what) => write!(f, "invalid perf record: {}", what),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// A cursor for reading native-endian fields from a record body.
+#[derive(Clone, Debug)]
+pub(crate) struct Cursor<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Cursor<'a> {
+    pub(crate) fn new(bytes: &'a [u8]) -> Self {
+        Cursor { bytes }
+    }
+
+    /// Return the number of bytes remaining.
+    pub(crate) fn len(&self) -> usize {
+        self.bytes.len()
+    }
+
+    pub(crate) fn bytes(&mut self, len: usize) -> Result<&'a [u8], ParseError> {
+        if len > self.bytes.len() {
+            return Err(ParseError::Truncated);
+        }
+        let (head, tail) = self.bytes.split_at(len);
+        self.bytes = tail;
+        Ok(head)
+    }
+
+    pub(crate) fn skip(&mut self, len: usize) -> Result<(), ParseError> {
+        self.bytes(len).map(|_| ())
+    }
+
+    pub(crate) fn array<const N: usize>(&mut self) -> Result<[u8; N], ParseError> {
+        let mut array = [0; N];
+        array.copy_from_slice(self.bytes(N)?);
+        Ok(array)
+    }
+
+    pub(crate) fn u8(&mut self) -> Result<u8, ParseError> {
+        Ok(self.bytes(1)?[0])
+    }
+
+    pub(crate) fn u16(&mut self) -> Result<u16, ParseError> {
+        self.array().map(u16::from_ne_bytes)
+    }
+
+    pub(crate) fn u32(&mut self) -> Result<u32, ParseError> {
+        self.array().map(u32::from_ne_bytes)
+    }
+
+    pub(crate) fn u64(&mut self) -> Result<u64, ParseError> {
+        self.array().map(u64::from_ne_bytes)
+    }
+
+    /// Read `count` native-endian `u64` values.
+    pub(crate) fn u64s(&mut self, count: u64) -> Result<Vec<u64>, ParseError> {
+        if count > self.bytes.len() as u64 / 8 {
+            return Err(ParseError::Truncated);
+        }
+        (0..count).map(|_| self.u64()).collect()
+    }
+
+    /// Read a NUL-terminated string padded to a multiple of eight bytes,
+    /// returning its contents without the NUL.
+    pub(crate) fn string(&mut self) -> Result<&'a [u8], ParseError> {
+        let len = self
+            .bytes
+            .iter()
+            .position(|&b| b == 0)
+            .ok_or(ParseError::Truncated)?;
+        let padded = (len + 1 + 7) & !7;
+        let string = &self.bytes[..len];
+        self.skip(padded.min(self.bytes.len()))?;
+        Ok(string)
+    }
+
+    /// Consume and return all remaining bytes.
+    pub(crate) fn rest(&mut self) -> &'a [u8] {
+        std::mem::take(&mut self.bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn header(type_: u32, misc: u32) -> perf_event_header {
+        perf_event_header {
+            type_,
+            misc: misc as u16,
+            size: 0,
+        }
+    }
+
+    #[test]
+    fn comm() {
+        let mut body = Vec::new();
+        body.extend_from_slice(&42_u32.to_ne_bytes());
+        body.extend_from_slice(&43_u32.to_ne_bytes());
+        body.extend_from_slice(b"rustc\0\0\0");
+        let record = Record::parse(
+            &header(
+                bindings::perf_event_type_PERF_RECORD_COMM,
+                bindings::PERF_RECORD_MISC_COMM_EXEC,
+            ),
+            &body,
+        )
+        .unwrap();
+        assert_eq!(
+            record,
+            Record::Comm {
+                pid: 42,
+                tid: 43,
+                comm: b"rustc",
+                exec: true
+            }
+        );
+    }
+
+    #[test]
+    fn mmap2_forms() {
+        let mut common = Vec::new();
+        for field in &[1_u32, 2] {
+            common.extend_from_slice(&field.to_ne_bytes());
+        }
+        for field in &[0x1000_u64, 0x2000, 0] {
+            common.extend_from_slice(&field.to_ne_bytes());
+        }
+        let mut trailer = Vec::new();
+        trailer.extend_from_slice(&5_u32.to_ne_bytes());
+        trailer.extend_from_slice(&2_u32.to_ne_bytes());
+        trailer.extend_from_slice(b"/bin/ls\0");
+
+        let mut inode = common.clone();
+        for field in &[8_u32, 1] {
+            inode.extend_from_slice(&field.to_ne_bytes());
+        }
+        for field in &[1234_u64, 7] {
+            inode.extend_from_slice(&field.to_ne_bytes());
+        }
+        inode.extend_from_slice(&trailer);
+        match Record::parse(
+            &header(bindings::perf_event_type_PERF_RECORD_MMAP2, 0),
+            &inode,
+        ) {
+            Ok(Record::Mmap2 {
+                file: Mmap2File::Inode {
+                    maj: 8, ino: 1234, ..
+                },
+                prot: 5,
+                filename: b"/bin/ls",
+                ..
+            }) => {}
+            other => panic!("unexpected {:?}", other),
+        }
+
+        let mut build_id = common;
+        build_id.extend_from_slice(&[3, 0, 0, 0]);
+        build_id.extend_from_slice(&[0xab; 20]);
+        build_id.extend_from_slice(&trailer);
+        match Record::parse(
+            &header(
+                bindings::perf_event_type_PERF_RECORD_MMAP2,
+                bindings::PERF_RECORD_MISC_MMAP_BUILD_ID,
+            ),
+            &build_id,
+        ) {
+            Ok(Record::Mmap2 {
+                file: Mmap2File::BuildId(&[0xab, 0xab, 0xab]),
+                flags: 2,
+                ..
+            }) => {}
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+
+    #[test]
+    fn unknown_and_truncated() {
+        let body = [1, 2, 3];
+        assert_eq!(
+            Record::parse(&header(1000, 5), &body),
+            Ok(Record::Unknown {
+                type_: 1000,
+                misc: 5,
+                body: &body
+            })
+        );
+        assert_eq!(
+            Record::parse(
+                &header(bindings::perf_event_type_PERF_RECORD_LOST, 0),
+                &body
+            ),
+            Err(ParseError::Truncated)
+        );
+    }
+
+    #[test]
+    fn sample_id_trailer() {
+        let mut attrs = perf_event_attr {
+            sample_type: bindings::perf_event_sample_format_PERF_SAMPLE_TID
+                | bindings::perf_event_sample_format_PERF_SAMPLE_TIME
+                | bindings::perf_event_sample_format_PERF_SAMPLE_CPU
+                | bindings::perf_event_sample_format_PERF_SAMPLE_IP,
+            ..Default::default()
+        };
+        attrs.set_sample_id_all_flag(true);
+        let parser = RecordParser::new(&attrs);
+        assert_eq!(parser.sample_id_size(), 24);
+
+        let mut body = Vec::new();
+        for field in &[10_u32, 11] {
+            body.extend_from_slice(&field.to_ne_bytes());
+        }
+        body.extend_from_slice(b"a.out\0\0\0");
+        for field in &[10_u32, 11] {
+            body.extend_from_slice(&field.to_ne_bytes());
+        }
+        body.extend_from_slice(&999_u64.to_ne_bytes());
+        body.extend_from_slice(&2_u32.to_ne_bytes());
+        body.extend_from_slice(&0_u32.to_ne_bytes());
+
+        let (record, sample_id) = parser
+            .parse(
+                &header(bindings::perf_event_type_PERF_RECORD_COMM, 0),
+                &body,
+            )
+            .unwrap();
+        assert!(matches!(record, Record::Comm { comm: b"a.out", .. }));
+        let sample_id = sample_id.unwrap();
+        assert_eq!(sample_id.pid, Some(10));
+        assert_eq!(sample_id.time, Some(999));
+        assert_eq!(sample_id.cpu, Some(2));
+        assert_eq!(sample_id.id, None);
+
+        // A switch record has no body apart from the trailer.
+        let (record, sample_id) = parser
+            .parse(
+                &header(bindings::perf_event_type_PERF_RECORD_SWITCH, 0),
+                &body[16..],
+            )
+            .unwrap();
+        assert!(matches!(record, Record::Switch { out: false, .. }));
+        assert_eq!(sample_id.unwrap().time, Some(999));
+
+        // Samples don't carry the trailer.
+        let (record, sample_id) = parser
+            .parse(
+                &header(bindings::perf_event_type_PERF_RECORD_SAMPLE, 0),
+                &body,
+            )
+            .unwrap();
+        assert_eq!(record, Record::Sample(&body));
+        assert_eq!(sample_id, None);
+    }
+}
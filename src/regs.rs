@@ -0,0 +1,174 @@
+//! Decoding register masks for `sample_regs_user` and `sample_regs_intr`.
+//!
+//! The `sample_regs_user` and `sample_regs_intr` fields of `perf_event_attr`
+//! are bitmasks of register indices from the architecture's
+//! `<asm/perf_regs.h>` header. On x86, for example, those are the
+//! `perf_event_x86_regs_PERF_REG_X86_*` constants in the [`bindings`]
+//! module; on AArch64, `perf_event_arm_regs_PERF_REG_ARM64_*`; and on RISC-V,
+//! `perf_event_riscv_regs_PERF_REG_RISCV_*`.
+//!
+//! When a sample includes registers, the kernel writes one `u64` per bit set
+//! in the mask, in order of increasing bit number. The [`RegisterIndices`]
+//! iterator visits the indices of a mask in that same order, so zipping it
+//! with a sample's `regs` array pairs each value with its register:
+//!
+//! ```
+//! use perf_event_open_sys::regs;
+//!
+//! let mask = (1 << 8) | (1 << 7) | (1 << 0);
+//! let values = [0x1000, 0x7ffc_0000, 0x40_1000];
+//!
+//! let decoded: Vec<(u32, u64)> = regs::decode(mask, &values).collect();
+//! assert_eq!(decoded, [(0, 0x1000), (7, 0x7ffc_0000), (8, 0x40_1000)]);
+//! ```
+//!
+//! Some registers occupy more than one bit. The x86 XMM registers, for
+//! example, are 128 bits wide, so each takes two consecutive indices, the
+//! low half first.
+//!
+//! [`bindings`]: crate::bindings
+
+/// Build a register mask from a list of register indices.
+///
+/// Return `None` if any index is 64 or more, and so has no bit in the mask.
+pub fn mask<I>(indices: I) -> Option<u64>
+where
+    I: IntoIterator,
+    I::Item: Into<u32>,
+{
+    indices.into_iter().try_fold(0, |mask, index| {
+        Some(mask | 1_u64.checked_shl(index.into())?)
+    })
+}
+
+/// Return an iterator over the register indices in `mask`, in the order the
+/// kernel dumps their values.
+pub fn indices(mask: u64) -> RegisterIndices {
+    RegisterIndices { mask }
+}
+
+/// Pair each register index in `mask` with its value from `values`, as
+/// written by the kernel into a sample's `regs` array.
+///
+/// If `values` is shorter than the number of bits set in `mask`, the
+/// iteration stops at the end of `values`.
+pub fn decode(mask: u64, values: &[u64]) -> impl Iterator<Item = (u32, u64)> + '_ {
+    indices(mask).zip(values.iter().copied())
+}
+
+/// An iterator over the register indices in a mask, in increasing order.
+///
+/// See [`indices`].
+#[derive(Clone, Debug)]
+pub struct RegisterIndices {
+    mask: u64,
+}
+
+impl Iterator for RegisterIndices {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<u32> {
+        if self.mask == 0 {
+            return None;
+        }
+        let index = self.mask.trailing_zeros();
+        self.mask &= self.mask - 1;
+        Some(index)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.mask.count_ones() as usize;
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for RegisterIndices {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bit_order() {
+        let m = mask([63_u32, 0, 17, 32, 33]).unwrap();
+        assert_eq!(indices(m).len(), 5);
+        assert_eq!(indices(m).collect::<Vec<_>>(), [0, 17, 32, 33, 63]);
+        assert_eq!(indices(0).next(), None);
+        assert_eq!(mask([1_u32, 64]), None);
+        assert_eq!(mask(Vec::<u32>::new()), Some(0));
+    }
+
+    #[cfg(any(target_arch = "x86_64", target_arch = "x86"))]
+    #[test]
+    fn x86_regs() {
+        use crate::bindings::*;
+
+        let m = mask([
+            perf_event_x86_regs_PERF_REG_X86_IP,
+            perf_event_x86_regs_PERF_REG_X86_SP,
+            perf_event_x86_regs_PERF_REG_X86_AX,
+        ])
+        .unwrap();
+        let values = [1, 2, 3];
+        assert_eq!(
+            decode(m, &values).collect::<Vec<_>>(),
+            [
+                (perf_event_x86_regs_PERF_REG_X86_AX, 1),
+                (perf_event_x86_regs_PERF_REG_X86_SP, 2),
+                (perf_event_x86_regs_PERF_REG_X86_IP, 3),
+            ]
+        );
+    }
+
+    #[cfg(target_arch = "aarch64")]
+    #[test]
+    fn arm64_regs() {
+        use crate::bindings::*;
+
+        let m = mask([
+            perf_event_arm_regs_PERF_REG_ARM64_PC,
+            perf_event_arm_regs_PERF_REG_ARM64_SP,
+            perf_event_arm_regs_PERF_REG_ARM64_X0,
+        ])
+        .unwrap();
+        let values = [1, 2, 3];
+        assert_eq!(
+            decode(m, &values).collect::<Vec<_>>(),
+            [
+                (perf_event_arm_regs_PERF_REG_ARM64_X0, 1),
+                (perf_event_arm_regs_PERF_REG_ARM64_SP, 2),
+                (perf_event_arm_regs_PERF_REG_ARM64_PC, 3),
+            ]
+        );
+        assert_eq!(
+            mask([perf_event_arm_regs_PERF_REG_ARM64_MAX]),
+            Some(1 << 33)
+        );
+    }
+
+    #[cfg(target_arch = "riscv64")]
+    #[test]
+    fn riscv_regs() {
+        use crate::bindings::*;
+
+        let m = mask([
+            perf_event_riscv_regs_PERF_REG_RISCV_SP,
+            perf_event_riscv_regs_PERF_REG_RISCV_A0,
+            perf_event_riscv_regs_PERF_REG_RISCV_PC,
+        ])
+        .unwrap();
+        let values = [1, 2, 3];
+        assert_eq!(
+            decode(m, &values).collect::<Vec<_>>(),
+            [
+                (perf_event_riscv_regs_PERF_REG_RISCV_PC, 1),
+                (perf_event_riscv_regs_PERF_REG_RISCV_SP, 2),
+                (perf_event_riscv_regs_PERF_REG_RISCV_A0, 3),
+            ]
+        );
+        assert_eq!(
+            mask([perf_event_riscv_regs_PERF_REG_RISCV_MAX]),
+            Some(1 << 32)
+        );
+    }
+}
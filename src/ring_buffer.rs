@@ -0,0 +1,370 @@
+//! Reading records from a `perf_event_open` file descriptor's ring buffer.
+//!
+//! When a perf event is sampling, or is asked to record events like `mmap` or
+//! `comm`, the kernel writes records into a ring buffer shared with userspace
+//! via `mmap`. The first page of the mapping is a [`perf_event_mmap_page`],
+//! which holds the ring's `data_head` and `data_tail` offsets; the ring itself
+//! follows, and is a power-of-two number of pages long.
+//!
+//! The kernel advances `data_head` as it writes records, and userspace
+//! advances `data_tail` as it consumes them. Userspace must load `data_head`
+//! with acquire ordering before reading the records it covers, and store
+//! `data_tail` with release ordering once it is done with them, so that the
+//! kernel doesn't overwrite records that are still being read.
+//!
+//! Each record begins with a [`perf_event_header`] giving its type and size.
+//! Records are written contiguously, so a record may wrap around the end of
+//! the ring; [`RingBuffer`] copies such records into a scratch buffer so that
+//! callers always see a contiguous byte slice.
+//!
+//! ```no_run
+//! use perf_event_open_sys::ring_buffer::RingBuffer;
+//! # fn example(fd: std::os::unix::io::BorrowedFd) -> std::io::Result<()> {
+//!
+//! // Map one metadata page plus eight data pages.
+//! let mut ring = RingBuffer::new(fd, 8)?;
+//! while let Some(record) = ring.next_record() {
+//!     println!("type {} with {} body bytes", record.header().type_, record.body().len());
+//!     // Dropping `record` releases its space back to the kernel.
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`perf_event_mmap_page`]: crate::bindings::perf_event_mmap_page
+//! [`perf_event_header`]: crate::bindings::perf_event_header
+
+use crate::bindings::{perf_event_header, perf_event_mmap_page};
+use std::io;
+use std::mem::size_of;
+use std::os::unix::io::{AsRawFd, BorrowedFd};
+use std::ptr::{self, addr_of_mut};
+use std::slice;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// A memory-mapped `perf_event_open` ring buffer.
+///
+/// The mapping is unmapped when the `RingBuffer` is dropped. It does not keep
+/// the file descriptor it was created from open; the mapping remains valid
+/// after the descriptor is closed.
+pub struct RingBuffer {
+    /// The start of the mapping, which is the metadata page.
+    page: *mut perf_event_mmap_page,
+
+    /// The length of the entire mapping, in bytes.
+    len: usize,
+
+    /// The start of the data area.
+    data: *const u8,
+
+    /// The length of the data area, in bytes. Always a power of two.
+    data_size: usize,
+
+    /// Storage for records that wrap around the end of the data area.
+    scratch: Vec<u8>,
+}
+
+// The mapping is not tied to any thread.
+unsafe impl Send for RingBuffer {}
+
+impl RingBuffer {
+    /// Map the ring buffer of the perf event `fd`, with `data_pages` pages of
+    /// data following the metadata page.
+    ///
+    /// The kernel requires `data_pages` to be a power of two; zero is
+    /// permitted, and maps only the metadata page, which is useful for
+    /// self-monitoring but never yields any records.
+    pub fn new(fd: BorrowedFd<'_>, data_pages: usize) -> io::Result<RingBuffer> {
+        if data_pages != 0 && !data_pages.is_power_of_two() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "ring buffer data page count must be a power of two",
+            ));
+        }
+
+        let page_size = page_size();
+        let len = (1 + data_pages) * page_size;
+        let base = unsafe {
+            libc::mmap(
+                ptr::null_mut(),
+                len,
+                libc::PROT_READ | libc::PROT_WRITE,
+                libc::MAP_SHARED,
+                fd.as_raw_fd(),
+                0,
+            )
+        };
+        if base == libc::MAP_FAILED {
+            return Err(io::Error::last_os_error());
+        }
+
+        let page = base as *mut perf_event_mmap_page;
+
+        // Kernels before 4.1 don't fill in `data_offset` and `data_size`, but
+        // always place the data area right after the metadata page.
+        let (data_offset, data_size) = unsafe {
+            match ((*page).data_offset as usize, (*page).data_size as usize) {
+                (_, 0) => (page_size, len - page_size),
+                (offset, size) => (offset, size),
+            }
+        };
+
+        Ok(RingBuffer {
+            page,
+            len,
+            data: unsafe { (base as *const u8).add(data_offset) },
+            data_size,
+            scratch: Vec::new(),
+        })
+    }
+
+    /// Return a pointer to the ring buffer's metadata page.
+    ///
+    /// The kernel updates this page concurrently, so fields other than
+    /// `data_head` and `data_tail` should be read following the protocol
+    /// described in `<linux/perf_event.h>`.
+    pub fn as_ptr(&self) -> *mut perf_event_mmap_page {
+        self.page
+    }
+
+    /// Return the size of the data area, in bytes.
+    pub fn data_size(&self) -> usize {
+        self.data_size
+    }
+
+    /// Return the next record in the ring, or `None` if the ring is empty.
+    ///
+    /// The record's space is released to the kernel when the returned
+    /// [`RawRecord`] is dropped.
+    pub fn next_record(&mut self) -> Option<RawRecord<'_>> {
+        if self.data_size == 0 {
+            return None;
+        }
+
+        let (head, tail) = unsafe {
+            let head = &*(addr_of_mut!((*self.page).data_head) as *const AtomicU64);
+            let tail = &*(addr_of_mut!((*self.page).data_tail) as *const AtomicU64);
+            (head, tail)
+        };
+
+        // The acquire load ensures that we see the record contents the kernel
+        // wrote before it advanced `data_head`. We are the only writer of
+        // `data_tail`, so a relaxed load suffices.
+        let head_pos = head.load(Ordering::Acquire);
+        let tail_pos = tail.load(Ordering::Relaxed);
+        if tail_pos == head_pos {
+            return None;
+        }
+
+        let (header, bytes) =
+            unsafe { record_at(self.data, self.data_size, tail_pos, &mut self.scratch) };
+
+        // A record too small to hold its own header, or one extending past
+        // what the kernel has written, means the ring is corrupt. Discard
+        // everything rather than loop forever.
+        let size = header.size as u64;
+        if size < size_of::<perf_event_header>() as u64 || size > head_pos - tail_pos {
+            tail.store(head_pos, Ordering::Release);
+            return None;
+        }
+
+        Some(RawRecord {
+            header,
+            bytes,
+            tail,
+            next_tail: tail_pos + size,
+        })
+    }
+}
+
+impl Drop for RingBuffer {
+    fn drop(&mut self) {
+        unsafe {
+            libc::munmap(self.page as *mut libc::c_void, self.len);
+        }
+    }
+}
+
+/// A record borrowed from a [`RingBuffer`].
+///
+/// Dropping a `RawRecord` advances the ring's `data_tail` past it, allowing the
+/// kernel to reuse its space.
+pub struct RawRecord<'a> {
+    header: perf_event_header,
+
+    /// The record's bytes, including the header.
+    bytes: &'a [u8],
+
+    tail: &'a AtomicU64,
+    next_tail: u64,
+}
+
+impl RawRecord<'_> {
+    /// Return the record's header.
+    pub fn header(&self) -> &perf_event_header {
+        &self.header
+    }
+
+    /// Return the record's body: its bytes following the header.
+    pub fn body(&self) -> &[u8] {
+        &self.bytes[size_of::<perf_event_header>()..]
+    }
+
+    /// Return the record's bytes, including the header.
+    pub fn bytes(&self) -> &[u8] {
+        self.bytes
+    }
+}
+
+impl Drop for RawRecord<'_> {
+    fn drop(&mut self) {
+        // The release store ensures we have finished reading the record
+        // before the kernel can see that its space is free.
+        self.tail.store(self.next_tail, Ordering::Release);
+    }
+}
+
+/// Return the header and bytes of the record at position `tail` in the ring
+/// of `data_size` bytes at `data`, copying it into `scratch` if it wraps
+/// around the end of the ring.
+///
+/// # Safety
+///
+/// `data` must point to `data_size` readable bytes, and `data_size` must be a
+/// power of two. The bytes of the record at `tail` must not change while the
+/// returned slice is in use.
+unsafe fn record_at(
+    data: *const u8,
+    data_size: usize,
+    tail: u64,
+    scratch: &mut Vec<u8>,
+) -> (perf_event_header, &[u8]) {
+    let offset = tail as usize & (data_size - 1);
+
+    let mut header_bytes = [0_u8; size_of::<perf_event_header>()];
+    copy_from_ring(data, data_size, offset, &mut header_bytes);
+    let header: perf_event_header = ptr::read_unaligned(header_bytes.as_ptr() as *const _);
+
+    // Clamp the length so that a corrupt header can't make us read beyond the
+    // data area. The caller checks the size against `data_head`.
+    let len = (header.size as usize).min(data_size);
+    if offset + len <= data_size {
+        (header, slice::from_raw_parts(data.add(offset), len))
+    } else {
+        scratch.clear();
+        scratch.resize(len, 0);
+        copy_from_ring(data, data_size, offset, scratch);
+        (header, &scratch[..])
+    }
+}
+
+/// Copy `dest.len()` bytes from the ring at `data` into `dest`, starting at
+/// `offset` and wrapping around the end of the ring if necessary.
+unsafe fn copy_from_ring(data: *const u8, data_size: usize, offset: usize, dest: &mut [u8]) {
+    let first = dest.len().min(data_size - offset);
+    ptr::copy_nonoverlapping(data.add(offset), dest.as_mut_ptr(), first);
+    ptr::copy_nonoverlapping(data, dest.as_mut_ptr().add(first), dest.len() - first);
+}
+
+fn page_size() -> usize {
+    unsafe { libc::sysconf(libc::_SC_PAGESIZE) as usize }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bindings;
+    use std::os::unix::io::AsFd;
+
+    fn push_record(ring: &mut [u8], pos: usize, type_: u32, body: &[u8]) -> usize {
+        let header = perf_event_header {
+            type_,
+            misc: 0,
+            size: (size_of::<perf_event_header>() + body.len()) as u16,
+        };
+        let header_bytes: [u8; 8] = unsafe { std::mem::transmute(header) };
+        let len = ring.len();
+        for (i, byte) in header_bytes.iter().chain(body).enumerate() {
+            ring[(pos + i) % len] = *byte;
+        }
+        pos + header.size as usize
+    }
+
+    #[test]
+    fn wrapped_record() {
+        let mut ring = vec![0_u8; 64];
+        let body: Vec<u8> = (1..=24).collect();
+        push_record(&mut ring, 48, 9, &body);
+
+        let mut scratch = Vec::new();
+        let (header, bytes) = unsafe { record_at(ring.as_ptr(), ring.len(), 48, &mut scratch) };
+        assert_eq!(header.type_, 9);
+        assert_eq!(header.size, 32);
+        assert_eq!(&bytes[8..], &body[..]);
+
+        // Positions are free-running, not reduced modulo the ring size.
+        let (header, bytes) =
+            unsafe { record_at(ring.as_ptr(), ring.len(), 48 + 64 * 3, &mut scratch) };
+        assert_eq!(header.type_, 9);
+        assert_eq!(&bytes[8..], &body[..]);
+    }
+
+    #[test]
+    fn unwrapped_record() {
+        let mut ring = vec![0_u8; 64];
+        let next = push_record(&mut ring, 0, 3, &[7; 16]);
+        assert_eq!(next, 24);
+        push_record(&mut ring, next, 4, &[8; 8]);
+
+        let mut scratch = Vec::new();
+        let (header, bytes) = unsafe { record_at(ring.as_ptr(), ring.len(), 24, &mut scratch) };
+        assert_eq!(header.type_, 4);
+        assert_eq!(bytes.as_ptr(), unsafe { ring.as_ptr().add(24) });
+        assert!(scratch.is_empty());
+    }
+
+    #[test]
+    fn live_samples() {
+        let mut attrs = bindings::perf_event_attr {
+            size: size_of::<bindings::perf_event_attr>() as u32,
+            type_: bindings::perf_type_id_PERF_TYPE_SOFTWARE,
+            config: bindings::perf_sw_ids_PERF_COUNT_SW_TASK_CLOCK as u64,
+            sample_type: bindings::perf_event_sample_format_PERF_SAMPLE_IP,
+            ..Default::default()
+        };
+        attrs.set_sample_period(100_000);
+        attrs.set_exclude_kernel_flag(true);
+        attrs.set_exclude_hv_flag(true);
+
+        let fd = match unsafe { crate::perf_event_open_fd(&mut attrs, 0, -1, -1, 0) } {
+            Ok(fd) => fd,
+            Err(err) if crate::test_util::unavailable(err.raw_os_error()) => return,
+            Err(err) => panic!("{}", err),
+        };
+        let mut ring = RingBuffer::new(fd.as_fd(), 4).unwrap();
+        assert_eq!(ring.data_size(), 4 * page_size());
+
+        let mut x = 0_u64;
+        for i in 0..10_000_000 {
+            x = x.wrapping_mul(31).wrapping_add(i);
+        }
+        std::hint::black_box(x);
+
+        let mut samples = 0;
+        while let Some(record) = ring.next_record() {
+            if record.header().type_ == bindings::perf_event_type_PERF_RECORD_SAMPLE {
+                assert_eq!(record.body().len(), 8);
+                samples += 1;
+            }
+        }
+        assert!(samples > 0);
+        assert!(ring.next_record().is_none());
+    }
+
+    #[test]
+    fn bad_page_count() {
+        let file = std::fs::File::open("/dev/null").unwrap();
+        let err = RingBuffer::new(file.as_fd(), 3).err().unwrap();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    }
+}
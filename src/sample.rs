@@ -0,0 +1,504 @@
+//! Decoding the bodies of `PERF_RECORD_SAMPLE` records.
+//!
+//! The contents of a sample depend on the `perf_event_attr` that opened the
+//! event: its `sample_type` bits select which fields are present, and its
+//! `read_format`, `branch_sample_type`, `sample_regs_user` and
+//! `sample_regs_intr` fields determine the layout of some of those fields. A
+//! [`SampleParser`] captures all of that from a `perf_event_attr`, and decodes
+//! sample bodies into [`Sample`] structs.
+//!
+//! ```
+//! use perf_event_open_sys::bindings;
+//! use perf_event_open_sys::sample::SampleParser;
+//!
+//! let mut attrs = bindings::perf_event_attr::default();
+//! attrs.sample_type = bindings::perf_event_sample_format_PERF_SAMPLE_IP
+//!     | bindings::perf_event_sample_format_PERF_SAMPLE_TID;
+//!
+//! let parser = SampleParser::new(&attrs);
+//!
+//! let mut body = Vec::new();
+//! body.extend_from_slice(&0x401000_u64.to_ne_bytes());
+//! body.extend_from_slice(&1234_u32.to_ne_bytes());
+//! body.extend_from_slice(&1235_u32.to_ne_bytes());
+//!
+//! let sample = parser.parse(&body).unwrap();
+//! assert_eq!(sample.ip, Some(0x401000));
+//! assert_eq!(sample.pid, Some(1234));
+//! assert_eq!(sample.tid, Some(1235));
+//! assert_eq!(sample.time, None);
+//! ```
+
+use crate::bindings::{self, perf_event_attr};
+use crate::read::{self, ReadValue};
+use crate::record::{Cursor, ParseError};
+use crate::regs;
+
+/// A decoder for `PERF_RECORD_SAMPLE` bodies.
+///
+/// See the [module documentation](self) for details.
+#[derive(Clone, Debug)]
+pub struct SampleParser {
+    sample_type: u64,
+    read_format: u64,
+    branch_hw_index: bool,
+    regs_user: u64,
+    regs_intr: u64,
+}
+
+/// A decoded sample.
+///
+/// Each field is `Some` if and only if the corresponding bit was set in the
+/// event's `sample_type`. Fields are listed in the order in which they appear
+/// in the sample.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Sample<'a> {
+    /// `PERF_SAMPLE_IDENTIFIER` or `PERF_SAMPLE_ID`: the id of the event that
+    /// produced the sample. If both are set, they hold the same value.
+    pub id: Option<u64>,
+
+    /// `PERF_SAMPLE_IP`: the instruction pointer.
+    pub ip: Option<u64>,
+
+    /// `PERF_SAMPLE_TID`: the process id.
+    pub pid: Option<u32>,
+
+    /// `PERF_SAMPLE_TID`: the thread id.
+    pub tid: Option<u32>,
+
+    /// `PERF_SAMPLE_TIME`: the timestamp.
+    pub time: Option<u64>,
+
+    /// `PERF_SAMPLE_ADDR`: the address involved, for events that have one.
+    pub addr: Option<u64>,
+
+    /// `PERF_SAMPLE_STREAM_ID`: the id of the event that produced the
+    /// sample, or of its parent if it was inherited.
+    pub stream_id: Option<u64>,
+
+    /// `PERF_SAMPLE_CPU`: the CPU on which the sample was taken.
+    pub cpu: Option<u32>,
+
+    /// `PERF_SAMPLE_PERIOD`: the current sampling period.
+    pub period: Option<u64>,
+
+    /// `PERF_SAMPLE_READ`: the counter values.
+    pub read: Option<ReadValue>,
+
+    /// `PERF_SAMPLE_CALLCHAIN`: the call chain, innermost frame first,
+    /// including `PERF_CONTEXT_*` markers.
+    pub callchain: Option<Vec<u64>>,
+
+    /// `PERF_SAMPLE_RAW`: raw, event-specific data, including any padding
+    /// the kernel added.
+    pub raw: Option<&'a [u8]>,
+
+    /// `PERF_SAMPLE_BRANCH_STACK`: the branch stack.
+    pub branch_stack: Option<BranchStack>,
+
+    /// `PERF_SAMPLE_REGS_USER`: the user-space registers selected by
+    /// `sample_regs_user`.
+    pub user_regs: Option<Regs>,
+
+    /// `PERF_SAMPLE_STACK_USER`: a copy of the user-space stack.
+    pub user_stack: Option<UserStack<'a>>,
+
+    /// `PERF_SAMPLE_WEIGHT` or `PERF_SAMPLE_WEIGHT_STRUCT`: the cost of the
+    /// sampled operation.
+    pub weight: Option<Weight>,
+
+    /// `PERF_SAMPLE_DATA_SRC`: the data source of a memory operation.
+    pub data_src: Option<u64>,
+
+    /// `PERF_SAMPLE_TRANSACTION`: transactional memory abort information.
+    pub transaction: Option<u64>,
+
+    /// `PERF_SAMPLE_REGS_INTR`: the registers selected by `sample_regs_intr`,
+    /// as of the interrupt or sampled instruction.
+    pub intr_regs: Option<Regs>,
+
+    /// `PERF_SAMPLE_PHYS_ADDR`: the physical address of `addr`.
+    pub phys_addr: Option<u64>,
+
+    /// `PERF_SAMPLE_CGROUP`: the id of the cgroup of the sampled task.
+    pub cgroup: Option<u64>,
+
+    /// `PERF_SAMPLE_DATA_PAGE_SIZE`: the page size of `addr`.
+    pub data_page_size: Option<u64>,
+
+    /// `PERF_SAMPLE_CODE_PAGE_SIZE`: the page size of `ip`.
+    pub code_page_size: Option<u64>,
+
+    /// `PERF_SAMPLE_AUX`: a snapshot of the AUX area.
+    pub aux: Option<&'a [u8]>,
+}
+
+/// A sampled branch stack.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BranchStack {
+    /// The index of the most recent branch in the hardware's branch buffer,
+    /// if `branch_sample_type` included `PERF_SAMPLE_BRANCH_HW_INDEX`.
+    pub hw_index: Option<u64>,
+
+    /// The branches, most recent first.
+    pub entries: Vec<BranchEntry>,
+}
+
+/// A single entry in a [`BranchStack`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct BranchEntry {
+    pub from: u64,
+    pub to: u64,
+
+    /// The `perf_branch_entry` bitfield: misprediction, cycle counts, branch
+    /// type and so on.
+    pub flags: u64,
+}
+
+/// Registers captured in a sample.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Regs {
+    /// One of the `perf_sample_regs_abi_*` constants. If this is
+    /// `PERF_SAMPLE_REGS_ABI_NONE`, no registers were captured.
+    pub abi: u64,
+
+    /// The mask of registers requested, from `sample_regs_user` or
+    /// `sample_regs_intr`.
+    pub mask: u64,
+
+    /// The register values, in order of increasing register index.
+    pub values: Vec<u64>,
+}
+
+impl Regs {
+    /// Return an iterator over `(index, value)` pairs for the captured
+    /// registers.
+    pub fn iter(&self) -> impl Iterator<Item = (u32, u64)> + '_ {
+        regs::decode(self.mask, &self.values)
+    }
+}
+
+/// A copy of the user-space stack captured in a sample.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct UserStack<'a> {
+    /// The bytes copied, of the size requested by `sample_stack_user`.
+    pub data: &'a [u8],
+
+    /// How many bytes of `data` are actually valid.
+    pub dyn_size: u64,
+}
+
+impl<'a> UserStack<'a> {
+    /// Return the valid portion of the captured stack.
+    pub fn bytes(&self) -> &'a [u8] {
+        &self.data[..(self.dyn_size as usize).min(self.data.len())]
+    }
+}
+
+/// A sample's weight.
+///
+/// With `PERF_SAMPLE_WEIGHT`, this is a single value; with
+/// `PERF_SAMPLE_WEIGHT_STRUCT`, it holds three separate values, whose meaning
+/// depends on the event.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Weight(pub u64);
+
+impl Weight {
+    /// The entire weight, for `PERF_SAMPLE_WEIGHT`.
+    pub fn full(self) -> u64 {
+        self.0
+    }
+
+    /// The first weight value, for `PERF_SAMPLE_WEIGHT_STRUCT`.
+    pub fn var1_dw(self) -> u32 {
+        self.0 as u32
+    }
+
+    /// The second weight value, for `PERF_SAMPLE_WEIGHT_STRUCT`.
+    pub fn var2_w(self) -> u16 {
+        (self.0 >> 32) as u16
+    }
+
+    /// The third weight value, for `PERF_SAMPLE_WEIGHT_STRUCT`.
+    pub fn var3_w(self) -> u16 {
+        (self.0 >> 48) as u16
+    }
+}
+
+impl SampleParser {
+    /// Return a parser for samples from an event opened with `attrs`.
+    pub fn new(attrs: &perf_event_attr) -> SampleParser {
+        SampleParser {
+            sample_type: attrs.sample_type,
+            read_format: attrs.read_format,
+            branch_hw_index: attrs.branch_sample_type
+                & bindings::perf_branch_sample_type_PERF_SAMPLE_BRANCH_HW_INDEX as u64
+                != 0,
+            regs_user: attrs.sample_regs_user,
+            regs_intr: attrs.sample_regs_intr,
+        }
+    }
+
+    fn has(&self, bit: u64) -> bool {
+        self.sample_type & bit != 0
+    }
+
+    /// Decode the body of a `PERF_RECORD_SAMPLE` record.
+    ///
+    /// Any bytes following the sample's fields are ignored.
+    pub fn parse<'a>(&self, body: &'a [u8]) -> Result<Sample<'a>, ParseError> {
+        use bindings::*;
+
+        let mut c = Cursor::new(body);
+        let mut sample = Sample::default();
+
+        // This follows the order in which `perf_output_sample` in the
+        // kernel's `kernel/events/core.c` writes the fields, which is also
+        // the order the perf_event_open(2) man page gives. The comment in
+        // `<linux/perf_event.h>` omits `PERF_SAMPLE_CGROUP` and lists
+        // `PERF_SAMPLE_AUX` too early.
+        if self.has(perf_event_sample_format_PERF_SAMPLE_IDENTIFIER) {
+            sample.id = Some(c.u64()?);
+        }
+        if self.has(perf_event_sample_format_PERF_SAMPLE_IP) {
+            sample.ip = Some(c.u64()?);
+        }
+        if self.has(perf_event_sample_format_PERF_SAMPLE_TID) {
+            sample.pid = Some(c.u32()?);
+            sample.tid = Some(c.u32()?);
+        }
+        if self.has(perf_event_sample_format_PERF_SAMPLE_TIME) {
+            sample.time = Some(c.u64()?);
+        }
+        if self.has(perf_event_sample_format_PERF_SAMPLE_ADDR) {
+            sample.addr = Some(c.u64()?);
+        }
+        if self.has(perf_event_sample_format_PERF_SAMPLE_ID) {
+            sample.id = Some(c.u64()?);
+        }
+        if self.has(perf_event_sample_format_PERF_SAMPLE_STREAM_ID) {
+            sample.stream_id = Some(c.u64()?);
+        }
+        if self.has(perf_event_sample_format_PERF_SAMPLE_CPU) {
+            sample.cpu = Some(c.u32()?);
+            c.skip(4)?;
+        }
+        if self.has(perf_event_sample_format_PERF_SAMPLE_PERIOD) {
+            sample.period = Some(c.u64()?);
+        }
+        if self.has(perf_event_sample_format_PERF_SAMPLE_READ) {
+            sample.read = Some(read::parse_cursor(self.read_format, &mut c)?);
+        }
+        if self.has(perf_event_sample_format_PERF_SAMPLE_CALLCHAIN) {
+            let nr = c.u64()?;
+            sample.callchain = Some(c.u64s(nr)?);
+        }
+        if self.has(perf_event_sample_format_PERF_SAMPLE_RAW) {
+            let size = c.u32()? as usize;
+            sample.raw = Some(c.bytes(size)?);
+        }
+        if self.has(perf_event_sample_format_PERF_SAMPLE_BRANCH_STACK) {
+            let nr = c.u64()?;
+            let hw_index = if self.branch_hw_index {
+                Some(c.u64()?)
+            } else {
+                None
+            };
+            if nr > c.len() as u64 / 24 {
+                return Err(ParseError::Truncated);
+            }
+            let entries = (0..nr)
+                .map(|_| {
+                    Ok(BranchEntry {
+                        from: c.u64()?,
+                        to: c.u64()?,
+                        flags: c.u64()?,
+                    })
+                })
+                .collect::<Result<_, ParseError>>()?;
+            sample.branch_stack = Some(BranchStack { hw_index, entries });
+        }
+        if self.has(perf_event_sample_format_PERF_SAMPLE_REGS_USER) {
+            sample.user_regs = Some(parse_regs(&mut c, self.regs_user)?);
+        }
+        if self.has(perf_event_sample_format_PERF_SAMPLE_STACK_USER) {
+            let size = c.u64()?;
+            if size > c.len() as u64 {
+                return Err(ParseError::Truncated);
+            }
+            let data = c.bytes(size as usize)?;
+            // The kernel omits `dyn_size` when it copied nothing.
+            let dyn_size = if size != 0 { c.u64()? } else { 0 };
+            sample.user_stack = Some(UserStack { data, dyn_size });
+        }
+        if self.has(perf_event_sample_format_PERF_SAMPLE_WEIGHT)
+            || self.has(perf_event_sample_format_PERF_SAMPLE_WEIGHT_STRUCT)
+        {
+            sample.weight = Some(Weight(c.u64()?));
+        }
+        if self.has(perf_event_sample_format_PERF_SAMPLE_DATA_SRC) {
+            sample.data_src = Some(c.u64()?);
+        }
+        if self.has(perf_event_sample_format_PERF_SAMPLE_TRANSACTION) {
+            sample.transaction = Some(c.u64()?);
+        }
+        if self.has(perf_event_sample_format_PERF_SAMPLE_REGS_INTR) {
+            sample.intr_regs = Some(parse_regs(&mut c, self.regs_intr)?);
+        }
+        if self.has(perf_event_sample_format_PERF_SAMPLE_PHYS_ADDR) {
+            sample.phys_addr = Some(c.u64()?);
+        }
+        if self.has(perf_event_sample_format_PERF_SAMPLE_CGROUP) {
+            sample.cgroup = Some(c.u64()?);
+        }
+        if self.has(perf_event_sample_format_PERF_SAMPLE_DATA_PAGE_SIZE) {
+            sample.data_page_size = Some(c.u64()?);
+        }
+        if self.has(perf_event_sample_format_PERF_SAMPLE_CODE_PAGE_SIZE) {
+            sample.code_page_size = Some(c.u64()?);
+        }
+        if self.has(perf_event_sample_format_PERF_SAMPLE_AUX) {
+            let size = c.u64()?;
+            if size > c.len() as u64 {
+                return Err(ParseError::Truncated);
+            }
+            sample.aux = Some(c.bytes(size as usize)?);
+        }
+
+        Ok(sample)
+    }
+}
+
+fn parse_regs(c: &mut Cursor, mask: u64) -> Result<Regs, ParseError> {
+    let abi = c.u64()?;
+    let values = if abi != bindings::perf_sample_regs_abi_PERF_SAMPLE_REGS_ABI_NONE as u64 {
+        c.u64s(mask.count_ones() as u64)?
+    } else {
+        Vec::new()
+    };
+    Ok(Regs { abi, mask, values })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bindings::*;
+
+    struct Body(Vec<u8>);
+
+    impl Body {
+        fn u32(&mut self, value: u32) -> &mut Self {
+            self.0.extend_from_slice(&value.to_ne_bytes());
+            self
+        }
+
+        fn u64(&mut self, value: u64) -> &mut Self {
+            self.0.extend_from_slice(&value.to_ne_bytes());
+            self
+        }
+    }
+
+    #[test]
+    fn field_order() {
+        let mut attrs = perf_event_attr {
+            sample_type: perf_event_sample_format_PERF_SAMPLE_IDENTIFIER
+                | perf_event_sample_format_PERF_SAMPLE_TIME
+                | perf_event_sample_format_PERF_SAMPLE_CPU
+                | perf_event_sample_format_PERF_SAMPLE_CALLCHAIN
+                | perf_event_sample_format_PERF_SAMPLE_BRANCH_STACK
+                | perf_event_sample_format_PERF_SAMPLE_REGS_USER
+                | perf_event_sample_format_PERF_SAMPLE_STACK_USER
+                | perf_event_sample_format_PERF_SAMPLE_CGROUP
+                | perf_event_sample_format_PERF_SAMPLE_CODE_PAGE_SIZE
+                | perf_event_sample_format_PERF_SAMPLE_AUX,
+            branch_sample_type: perf_branch_sample_type_PERF_SAMPLE_BRANCH_HW_INDEX as u64,
+            sample_regs_user: 0b101,
+            ..Default::default()
+        };
+        attrs.set_sample_period(1000);
+
+        let mut body = Body(Vec::new());
+        body.u64(77) // identifier
+            .u64(123_456) // time
+            .u32(3) // cpu
+            .u32(0) // reserved
+            .u64(2) // callchain nr
+            .u64(0x10)
+            .u64(0x20)
+            .u64(1) // branch nr
+            .u64(9) // hw_idx
+            .u64(0x100) // from
+            .u64(0x200) // to
+            .u64(0) // flags
+            .u64(perf_sample_regs_abi_PERF_SAMPLE_REGS_ABI_64 as u64)
+            .u64(0xaaaa)
+            .u64(0xcccc)
+            .u64(8) // user stack size
+            .u64(0x0102_0304_0506_0708)
+            .u64(4) // dyn_size
+            .u64(55) // cgroup
+            .u64(4096) // code page size
+            .u64(0); // aux size
+
+        let sample = SampleParser::new(&attrs).parse(&body.0).unwrap();
+        assert_eq!(sample.id, Some(77));
+        assert_eq!(sample.time, Some(123_456));
+        assert_eq!(sample.cpu, Some(3));
+        assert_eq!(sample.callchain, Some(vec![0x10, 0x20]));
+        let branches = sample.branch_stack.unwrap();
+        assert_eq!(branches.hw_index, Some(9));
+        assert_eq!(branches.entries[0].to, 0x200);
+        let regs = sample.user_regs.unwrap();
+        assert_eq!(regs.iter().collect::<Vec<_>>(), [(0, 0xaaaa), (2, 0xcccc)]);
+        assert_eq!(sample.user_stack.unwrap().bytes().len(), 4);
+        assert_eq!(sample.cgroup, Some(55));
+        assert_eq!(sample.code_page_size, Some(4096));
+        assert_eq!(sample.aux, Some(&[][..]));
+        assert_eq!(sample.ip, None);
+    }
+
+    #[test]
+    fn read_group() {
+        let attrs = perf_event_attr {
+            sample_type: perf_event_sample_format_PERF_SAMPLE_READ
+                | perf_event_sample_format_PERF_SAMPLE_PERIOD,
+            read_format: (perf_event_read_format_PERF_FORMAT_GROUP
+                | perf_event_read_format_PERF_FORMAT_ID
+                | perf_event_read_format_PERF_FORMAT_TOTAL_TIME_ENABLED)
+                as u64,
+            ..Default::default()
+        };
+
+        let mut body = Body(Vec::new());
+        body.u64(500) // period
+            .u64(2) // nr
+            .u64(1000) // time enabled
+            .u64(11)
+            .u64(1)
+            .u64(22)
+            .u64(2)
+            .u64(0xdead); // trailing bytes, ignored
+
+        let sample = SampleParser::new(&attrs).parse(&body.0).unwrap();
+        assert_eq!(sample.period, Some(500));
+        let read = sample.read.unwrap();
+        assert_eq!(read.time_enabled(), Some(1000));
+        assert_eq!(read.values()[1].value, 22);
+        assert_eq!(read.values()[1].id, Some(2));
+    }
+
+    #[test]
+    fn truncated() {
+        let attrs = perf_event_attr {
+            sample_type: perf_event_sample_format_PERF_SAMPLE_CALLCHAIN,
+            ..Default::default()
+        };
+        let mut body = Body(Vec::new());
+        body.u64(u64::MAX);
+        assert_eq!(
+            SampleParser::new(&attrs).parse(&body.0),
+            Err(ParseError::Truncated)
+        );
+    }
+}
@@ -0,0 +1,362 @@
+//! Reading a thread's own counter without a system call.
+//!
+//! Reading a perf event's file descriptor costs a system call. When a thread
+//! measures itself, and the event is counting on a hardware counter, the
+//! kernel can instead let it read the counter directly, with the x86
+//! `rdpmc` instruction. The event's [`perf_event_mmap_page`] says which
+//! counter to read, how wide it is, and what to add to it:
+//!
+//! ```text
+//! do {
+//!     seq = pc->lock;
+//!     barrier();
+//!     idx = pc->index;
+//!     count = pc->offset;
+//!     if (pc->cap_user_rdpmc && idx) {
+//!         pmc = rdpmc(idx - 1);
+//!         pmc <<= 64 - pc->pmc_width;
+//!         pmc >>= 64 - pc->pmc_width;
+//!         count += pmc;
+//!     }
+//!     barrier();
+//! } while (pc->lock != seq);
+//! ```
+//!
+//! The kernel increments `lock` around every update of the page, so the loop
+//! retries if the thread was preempted or migrated partway through. An
+//! `index` of zero means the event isn't on a hardware counter right now:
+//! it's a software event, or it's disabled, or it has been multiplexed off.
+//!
+//! A [`SelfCounter`] follows this loop, and falls back to `read(2)` whenever
+//! `rdpmc` isn't available:
+//!
+//! ```no_run
+//! use perf_event_open_sys::bindings::*;
+//! use perf_event_open_sys::self_counter::SelfCounter;
+//!
+//! let mut attrs = perf_event_attr::default();
+//! attrs.size = std::mem::size_of::<perf_event_attr>() as u32;
+//! attrs.type_ = perf_type_id_PERF_TYPE_HARDWARE;
+//! attrs.config = perf_hw_id_PERF_COUNT_HW_INSTRUCTIONS as u64;
+//! attrs.set_exclude_kernel_flag(true);
+//!
+//! let counter = unsafe { SelfCounter::open(&mut attrs)? };
+//! let before = counter.read()?;
+//! // ... code to measure ...
+//! let after = counter.read()?;
+//! println!("{} instructions", after - before);
+//! # Ok::<(), std::io::Error>(())
+//! ```
+//!
+//! Whether user-space `rdpmc` is permitted is controlled by
+//! `/sys/bus/event_source/devices/cpu/rdpmc`; virtual machines often don't
+//! permit it at all.
+//!
+//! [`perf_event_mmap_page`]: crate::bindings::perf_event_mmap_page
+
+use crate::bindings::{perf_event_attr, perf_event_mmap_page};
+use crate::ioctls::{self, Scope};
+use crate::perf_event_open_fd;
+use crate::read;
+use crate::ring_buffer::RingBuffer;
+use std::io;
+use std::marker::PhantomData;
+use std::os::unix::io::{AsFd, BorrowedFd, OwnedFd};
+use std::ptr::{addr_of, read_volatile};
+use std::sync::atomic::{compiler_fence, Ordering};
+
+/// A counter measuring the calling thread, which it can read without a
+/// system call.
+///
+/// A `SelfCounter` is neither `Send` nor `Sync`: `rdpmc` reads the counters
+/// of whatever CPU the caller is running on, so only the thread being
+/// measured may read it.
+pub struct SelfCounter {
+    fd: OwnedFd,
+
+    /// The event's metadata page, with no data area.
+    page: RingBuffer,
+
+    read_format: u64,
+    _not_send: PhantomData<*const ()>,
+}
+
+impl SelfCounter {
+    /// Open `attrs` to count the calling thread on any CPU, and map its
+    /// metadata page.
+    ///
+    /// # Safety
+    ///
+    /// The measurements and other behaviors requested by `attrs` must be safe.
+    pub unsafe fn open(attrs: &mut perf_event_attr) -> io::Result<SelfCounter> {
+        let fd = perf_event_open_fd(attrs, 0, -1, -1, 0)?;
+        let page = RingBuffer::new(fd.as_fd(), 0)?;
+        Ok(SelfCounter {
+            fd,
+            page,
+            read_format: attrs.read_format,
+            _not_send: PhantomData,
+        })
+    }
+
+    /// Return the event's file descriptor.
+    pub fn fd(&self) -> BorrowedFd<'_> {
+        self.fd.as_fd()
+    }
+
+    /// Enable the counter.
+    pub fn enable(&self) -> io::Result<()> {
+        ioctls::enable(self.fd(), Scope::Event)
+    }
+
+    /// Disable the counter.
+    pub fn disable(&self) -> io::Result<()> {
+        ioctls::disable(self.fd(), Scope::Event)
+    }
+
+    /// Reset the counter to zero.
+    pub fn reset(&self) -> io::Result<()> {
+        ioctls::reset(self.fd(), Scope::Event)
+    }
+
+    /// Return true if the kernel currently lets this thread read the counter
+    /// with `rdpmc`: it permits user-space `rdpmc`, and the event is on a
+    /// hardware counter. This can change as the event is scheduled on and off
+    /// the PMU.
+    pub fn rdpmc_available(&self) -> bool {
+        let page = self.page.as_ptr();
+        unsafe {
+            read_locked(page, || {
+                let (available, index) = page_snapshot(page);
+                available && index != 0
+            })
+        }
+    }
+
+    /// Return the counter's current value.
+    ///
+    /// This reads the counter with `rdpmc` when the kernel permits it, and
+    /// with [`SelfCounter::read_syscall`] otherwise.
+    #[inline]
+    pub fn read(&self) -> io::Result<u64> {
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        if let Some(count) = unsafe { read_page(self.page.as_ptr(), |counter| rdpmc(counter)) } {
+            return Ok(count);
+        }
+        self.read_syscall()
+    }
+
+    /// Return the counter's current value, using `read(2)`.
+    pub fn read_syscall(&self) -> io::Result<u64> {
+        let value = read::read_value(self.fd(), self.read_format, 1)?;
+        Ok(value.values()[0].value)
+    }
+}
+
+/// Return whether `cap_user_rdpmc` is set, and `index`, from `page`, without
+/// regard to the seqlock.
+unsafe fn page_snapshot(page: *const perf_event_mmap_page) -> (bool, u32) {
+    let caps = read_volatile(addr_of!((*page).__bindgen_anon_1));
+    let rdpmc = caps.__bindgen_anon_1.cap_user_rdpmc() != 0;
+    (rdpmc, read_volatile(addr_of!((*page).index)))
+}
+
+/// Call `read` until the seqlock of `page` shows that the kernel didn't
+/// update the page while it ran, and return its last result.
+unsafe fn read_locked<R, F>(page: *const perf_event_mmap_page, mut read: F) -> R
+where
+    F: FnMut() -> R,
+{
+    loop {
+        let seq = read_volatile(addr_of!((*page).lock));
+        compiler_fence(Ordering::SeqCst);
+        let result = read();
+        compiler_fence(Ordering::SeqCst);
+        if read_volatile(addr_of!((*page).lock)) == seq {
+            return result;
+        }
+    }
+}
+
+/// Follow the seqlock protocol to read the counter described by `page`,
+/// using `rdpmc` to read hardware counters.
+///
+/// Return `None` if the kernel doesn't permit reading the counter directly,
+/// or the event isn't on a hardware counter.
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[inline]
+unsafe fn read_page<F>(page: *const perf_event_mmap_page, rdpmc: F) -> Option<u64>
+where
+    F: Fn(u32) -> u64,
+{
+    read_locked(page, || {
+        let (available, index) = page_snapshot(page);
+        if !available || index == 0 {
+            return None;
+        }
+        let offset = read_volatile(addr_of!((*page).offset));
+        let width = read_volatile(addr_of!((*page).pmc_width));
+        Some(offset.wrapping_add(sign_extend(rdpmc(index - 1), width)) as u64)
+    })
+}
+
+/// Treat the low `width` bits of `value` as a signed integer.
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+fn sign_extend(value: u64, width: u16) -> i64 {
+    let shift = 64 - u32::from(width.clamp(1, 64));
+    ((value << shift) as i64) >> shift
+}
+
+/// Read hardware performance counter `counter`.
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[inline]
+unsafe fn rdpmc(counter: u32) -> u64 {
+    let (low, high): (u32, u32);
+    std::arch::asm!(
+        "rdpmc",
+        in("ecx") counter,
+        out("eax") low,
+        out("edx") high,
+        options(nostack, preserves_flags),
+    );
+    (high as u64) << 32 | low as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bindings::*;
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    use std::cell::Cell;
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    use std::ptr::addr_of_mut;
+
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    fn fake_page(rdpmc: bool, index: u32, offset: i64, width: u16) -> Box<perf_event_mmap_page> {
+        let mut page: Box<perf_event_mmap_page> = Box::new(unsafe { std::mem::zeroed() });
+        unsafe {
+            page.__bindgen_anon_1
+                .__bindgen_anon_1
+                .set_cap_user_rdpmc(rdpmc as u64)
+        };
+        page.index = index;
+        page.offset = offset;
+        page.pmc_width = width;
+        page
+    }
+
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    #[test]
+    fn sign_extension() {
+        assert_eq!(sign_extend(0x7fff_ffff_ffff, 48), 0x7fff_ffff_ffff);
+        assert_eq!(sign_extend(0xffff_ffff_ffff, 48), -1);
+        assert_eq!(sign_extend(0x8000_0000_0000, 48), -0x8000_0000_0000);
+        // Bits above the counter's width are garbage, and ignored.
+        assert_eq!(sign_extend(0xdead_0000_0000_0005, 48), 5);
+        assert_eq!(sign_extend(u64::MAX, 64), -1);
+    }
+
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    #[test]
+    fn page_protocol() {
+        let page = fake_page(true, 3, 1000, 48);
+        let counters = Cell::new(vec![]);
+        let rdpmc = |counter| {
+            let mut read = counters.take();
+            read.push(counter);
+            counters.set(read);
+            0xffff_ffff_fff6 // -10, in 48 bits
+        };
+        assert_eq!(unsafe { read_page(&*page, rdpmc) }, Some(990));
+        assert_eq!(counters.take(), [2]);
+
+        // No rdpmc, or not on a hardware counter: fall back.
+        let page = fake_page(false, 3, 1000, 48);
+        assert_eq!(unsafe { read_page(&*page, |_| unreachable!()) }, None);
+        let page = fake_page(true, 0, 1000, 48);
+        assert_eq!(unsafe { read_page(&*page, |_| unreachable!()) }, None);
+    }
+
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    #[test]
+    fn seqlock_retry() {
+        let mut page = fake_page(true, 1, 100, 48);
+        let page_ptr = addr_of_mut!(*page);
+        let reads = Cell::new(0);
+        let rdpmc = |_| {
+            reads.set(reads.get() + 1);
+            if reads.get() == 1 {
+                // The kernel updates the page while we're reading it.
+                unsafe {
+                    (*page_ptr).lock += 2;
+                    (*page_ptr).offset = 200;
+                }
+            }
+            5
+        };
+        assert_eq!(unsafe { read_page(page_ptr, rdpmc) }, Some(205));
+        assert_eq!(reads.get(), 2);
+    }
+
+    /// Open a counter, or return `None` if this system can't count the
+    /// event.
+    fn counter(type_: u32, config: u32) -> Option<SelfCounter> {
+        let mut attrs = perf_event_attr {
+            size: std::mem::size_of::<perf_event_attr>() as u32,
+            type_,
+            config: config as u64,
+            ..Default::default()
+        };
+        attrs.set_exclude_kernel_flag(true);
+        attrs.set_exclude_hv_flag(true);
+        match unsafe { SelfCounter::open(&mut attrs) } {
+            Ok(counter) => Some(counter),
+            Err(err) if crate::test_util::unavailable(err.raw_os_error()) => None,
+            Err(err) => panic!("{}", err),
+        }
+    }
+
+    #[test]
+    fn software_fallback() {
+        let counter = match counter(
+            perf_type_id_PERF_TYPE_SOFTWARE,
+            perf_sw_ids_PERF_COUNT_SW_TASK_CLOCK,
+        ) {
+            Some(counter) => counter,
+            None => return,
+        };
+        // Software events never have a hardware counter index.
+        assert_eq!(unsafe { page_snapshot(counter.page.as_ptr()).1 }, 0);
+        assert!(!counter.rdpmc_available());
+
+        let first = counter.read().unwrap();
+        let mut x = 0_u64;
+        for i in 0..1_000_000 {
+            x = x.wrapping_mul(31).wrapping_add(i);
+        }
+        std::hint::black_box(x);
+        let second = counter.read().unwrap();
+        assert!(second > first);
+        assert!(counter.read_syscall().unwrap() >= second);
+
+        counter.disable().unwrap();
+        counter.reset().unwrap();
+        assert_eq!(counter.read().unwrap(), 0);
+    }
+
+    #[test]
+    fn hardware_rdpmc() {
+        let counter = match counter(
+            perf_type_id_PERF_TYPE_HARDWARE,
+            perf_hw_id_PERF_COUNT_HW_INSTRUCTIONS,
+        ) {
+            Some(counter) => counter,
+            // Many virtual machines have no PMU.
+            None => return,
+        };
+        let direct = counter.read().unwrap();
+        let syscall = counter.read_syscall().unwrap();
+        let again = counter.read().unwrap();
+        assert!(direct <= syscall && syscall <= again);
+    }
+}
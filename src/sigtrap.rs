@@ -0,0 +1,321 @@
+//! Synchronous `SIGTRAP` delivery for perf events.
+//!
+//! Since Linux 5.13, setting the `sigtrap` flag in a `perf_event_attr` makes
+//! the kernel send a synchronous `SIGTRAP` to the thread that triggered each
+//! overflow of the event, instead of (or as well as) writing a sample. The
+//! `siginfo_t` carries `si_code == TRAP_PERF` and some perf-specific fields:
+//!
+//! -   `si_addr`: the address of the access, for breakpoints.
+//!
+//! -   `si_perf_data`: the event's `sig_data` field, which lets a handler tell
+//!     events apart.
+//!
+//! -   `si_perf_type`: the event's `type_`, like `PERF_TYPE_BREAKPOINT`.
+//!
+//! -   `si_perf_flags`: flags like `TRAP_PERF_FLAG_ASYNC`. This was added in
+//!     Linux 5.18; older kernels leave it zero.
+//!
+//! The kernel requires `remove_on_exec` to be set along with `sigtrap`, so a
+//! new program image doesn't receive signals it doesn't expect.
+//!
+//! This module installs a `SIGTRAP` handler that decodes those fields into a
+//! [`PerfSignal`] and passes it to the callback registered for its
+//! `sig_data`:
+//!
+//! ```no_run
+//! use perf_event_open_sys::breakpoint::Breakpoint;
+//! use perf_event_open_sys::sigtrap::{self, PerfSignal};
+//!
+//! fn on_write(signal: &PerfSignal) {
+//!     // Runs in signal handler context: async-signal-safe code only!
+//! }
+//!
+//! static WATCHED: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(0);
+//!
+//! sigtrap::install()?;
+//! sigtrap::register(42, on_write)?;
+//! let mut attrs = Breakpoint::write(WATCHED.as_ptr() as u64, 8).attr()?;
+//! attrs.set_sample_period(1);
+//! sigtrap::configure(&mut attrs, 42);
+//! // ... open the event and enable it
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+//!
+//! Callbacks run in signal handler context, so they must restrict themselves
+//! to async-signal-safe operations: no allocation, no locks.
+
+use crate::bindings::perf_event_attr;
+use libc::{c_int, c_ulong, c_void};
+use std::io;
+use std::mem::MaybeUninit;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+/// The `si_code` of a `SIGTRAP` sent for a perf event.
+pub const TRAP_PERF: c_int = 6;
+
+/// `si_perf_flags` bit: the signal was delivered asynchronously, not in the
+/// context of the access that triggered it.
+pub const TRAP_PERF_FLAG_ASYNC: u32 = 1 << 31;
+
+/// The maximum number of callbacks that can be registered at once.
+pub const MAX_CALLBACKS: usize = 64;
+
+/// The perf-specific contents of a `SIGTRAP`'s `siginfo_t`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PerfSignal {
+    /// `si_addr`: for breakpoints, the address accessed.
+    pub addr: u64,
+
+    /// `si_perf_data`: the event's `sig_data`.
+    pub data: u64,
+
+    /// `si_perf_type`: the event's `type_`.
+    pub type_: u32,
+
+    /// `si_perf_flags`, or zero on kernels older than 5.18.
+    pub flags: u32,
+}
+
+/// The start of `siginfo_t`, as laid out for `TRAP_PERF` signals.
+///
+/// In the kernel's `_sigfault` union member, `_perf` follows `si_addr`.
+#[repr(C)]
+struct PerfSigInfo {
+    si_signo: c_int,
+    si_errno: c_int,
+    si_code: c_int,
+    si_addr: *mut c_void,
+    si_perf_data: c_ulong,
+    si_perf_type: u32,
+    si_perf_flags: u32,
+}
+
+impl PerfSignal {
+    /// Decode `info`, returning `None` if it is not a perf `SIGTRAP`.
+    ///
+    /// # Safety
+    ///
+    /// `info` must point to a valid `siginfo_t`.
+    #[allow(clippy::useless_conversion)] // `c_ulong` is `u32` on 32-bit targets
+    pub unsafe fn from_siginfo(info: *const libc::siginfo_t) -> Option<PerfSignal> {
+        let info = &*(info as *const PerfSigInfo);
+        if info.si_signo != libc::SIGTRAP || info.si_code != TRAP_PERF {
+            return None;
+        }
+        Some(PerfSignal {
+            addr: info.si_addr as u64,
+            data: u64::from(info.si_perf_data),
+            type_: info.si_perf_type,
+            flags: info.si_perf_flags,
+        })
+    }
+}
+
+/// A function to call when a perf `SIGTRAP` arrives.
+///
+/// This runs in signal handler context.
+pub type Callback = fn(&PerfSignal);
+
+/// Set `attrs` to deliver a `SIGTRAP` carrying `sig_data` on each overflow.
+///
+/// This sets the `sigtrap` and `remove_on_exec` flags and the `sig_data`
+/// field. The caller must still choose a `sample_period`.
+pub fn configure(attrs: &mut perf_event_attr, sig_data: u64) {
+    attrs.set_sigtrap_flag(true);
+    attrs.set_remove_on_exec_flag(true);
+    attrs.sig_data = sig_data;
+}
+
+/// One registered callback. A `callback` of zero marks a free slot.
+struct Slot {
+    key: AtomicU64,
+    callback: AtomicUsize,
+}
+
+#[allow(clippy::declare_interior_mutable_const)]
+const EMPTY_SLOT: Slot = Slot {
+    key: AtomicU64::new(0),
+    callback: AtomicUsize::new(0),
+};
+
+/// The callback table. The handler only reads it, with atomic loads;
+/// `REGISTRY_LOCK` serializes changes.
+static SLOTS: [Slot; MAX_CALLBACKS] = [EMPTY_SLOT; MAX_CALLBACKS];
+static REGISTRY_LOCK: Mutex<()> = Mutex::new(());
+
+static INSTALLED: AtomicBool = AtomicBool::new(false);
+static mut PREVIOUS: MaybeUninit<libc::sigaction> = MaybeUninit::uninit();
+
+/// Install the `SIGTRAP` handler, if it isn't already installed.
+///
+/// `SIGTRAP`s that aren't from perf events, or whose `sig_data` has no
+/// registered callback, are passed to the handler that was installed before,
+/// if any.
+pub fn install() -> io::Result<()> {
+    let _guard = REGISTRY_LOCK.lock().unwrap_or_else(|err| err.into_inner());
+    if INSTALLED.load(Ordering::Acquire) {
+        return Ok(());
+    }
+    unsafe {
+        let mut action: libc::sigaction = std::mem::zeroed();
+        action.sa_sigaction = handler as *const () as usize;
+        action.sa_flags = libc::SA_SIGINFO | libc::SA_RESTART;
+        libc::sigemptyset(&mut action.sa_mask);
+        let previous = std::ptr::addr_of_mut!(PREVIOUS) as *mut libc::sigaction;
+        if libc::sigaction(libc::SIGTRAP, &action, previous) < 0 {
+            return Err(io::Error::last_os_error());
+        }
+    }
+    INSTALLED.store(true, Ordering::Release);
+    Ok(())
+}
+
+/// Call `callback` for each perf `SIGTRAP` whose `si_perf_data` is
+/// `sig_data`.
+///
+/// Replace any callback already registered for `sig_data`. Return an error
+/// if [`MAX_CALLBACKS`] callbacks are already registered.
+pub fn register(sig_data: u64, callback: Callback) -> io::Result<()> {
+    let _guard = REGISTRY_LOCK.lock().unwrap_or_else(|err| err.into_inner());
+    let existing = SLOTS.iter().find(|slot| {
+        slot.callback.load(Ordering::Relaxed) != 0 && slot.key.load(Ordering::Relaxed) == sig_data
+    });
+    let slot = match existing {
+        Some(slot) => slot,
+        None => SLOTS
+            .iter()
+            .find(|slot| slot.callback.load(Ordering::Relaxed) == 0)
+            .ok_or_else(|| io::Error::other("too many SIGTRAP callbacks"))?,
+    };
+    slot.key.store(sig_data, Ordering::Relaxed);
+    slot.callback.store(callback as usize, Ordering::Release);
+    Ok(())
+}
+
+/// Remove the callback for `sig_data`, returning it if there was one.
+pub fn unregister(sig_data: u64) -> Option<Callback> {
+    let _guard = REGISTRY_LOCK.lock().unwrap_or_else(|err| err.into_inner());
+    for slot in &SLOTS {
+        let callback = slot.callback.load(Ordering::Relaxed);
+        if callback != 0 && slot.key.load(Ordering::Relaxed) == sig_data {
+            slot.callback.store(0, Ordering::Release);
+            return Some(unsafe { std::mem::transmute::<usize, Callback>(callback) });
+        }
+    }
+    None
+}
+
+fn lookup(sig_data: u64) -> Option<Callback> {
+    SLOTS.iter().find_map(|slot| {
+        let callback = slot.callback.load(Ordering::Acquire);
+        if callback != 0 && slot.key.load(Ordering::Relaxed) == sig_data {
+            Some(unsafe { std::mem::transmute::<usize, Callback>(callback) })
+        } else {
+            None
+        }
+    })
+}
+
+extern "C" fn handler(signo: c_int, info: *mut libc::siginfo_t, context: *mut c_void) {
+    if let Some(signal) = unsafe { PerfSignal::from_siginfo(info) } {
+        if let Some(callback) = lookup(signal.data) {
+            callback(&signal);
+            return;
+        }
+    }
+    unsafe { chain(signo, info, context) }
+}
+
+/// Pass a signal we didn't handle to the previously installed handler.
+unsafe fn chain(signo: c_int, info: *mut libc::siginfo_t, context: *mut c_void) {
+    let previous = &*(std::ptr::addr_of!(PREVIOUS) as *const libc::sigaction);
+    match previous.sa_sigaction {
+        libc::SIG_IGN => {}
+        libc::SIG_DFL => {
+            // Restore the default action and let it run.
+            libc::signal(signo, libc::SIG_DFL);
+            libc::raise(signo);
+        }
+        f if previous.sa_flags & libc::SA_SIGINFO != 0 => {
+            let f: extern "C" fn(c_int, *mut libc::siginfo_t, *mut c_void) = std::mem::transmute(f);
+            f(signo, info, context);
+        }
+        f => {
+            let f: extern "C" fn(c_int) = std::mem::transmute(f);
+            f(signo);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::breakpoint::Breakpoint;
+    use crate::{bindings, ioctls, perf_event_open_fd};
+    use std::os::unix::io::AsFd;
+
+    static HITS: AtomicU64 = AtomicU64::new(0);
+    static LAST_TYPE: AtomicU64 = AtomicU64::new(u64::MAX);
+    static LAST_ADDR: AtomicU64 = AtomicU64::new(0);
+
+    fn on_hit(signal: &PerfSignal) {
+        HITS.fetch_add(1, Ordering::SeqCst);
+        LAST_TYPE.store(signal.type_ as u64, Ordering::SeqCst);
+        LAST_ADDR.store(signal.addr, Ordering::SeqCst);
+    }
+
+    #[test]
+    fn registry() {
+        fn other(_: &PerfSignal) {}
+
+        register(0x5eed_0001, other).unwrap();
+        assert!(lookup(0x5eed_0001).is_some());
+        assert!(lookup(0x5eed_0002).is_none());
+        assert!(unregister(0x5eed_0001).is_some());
+        assert!(unregister(0x5eed_0001).is_none());
+        assert!(lookup(0x5eed_0001).is_none());
+    }
+
+    #[test]
+    fn breakpoint_signal() {
+        const SIG_DATA: u64 = 0xfeed_face;
+
+        let mut local = 0_u64;
+        let mut attrs = Breakpoint::write(&mut local as *mut u64 as u64, 8)
+            .attr()
+            .unwrap();
+        attrs.set_sample_period(1);
+        attrs.set_disabled_flag(true);
+        attrs.set_exclude_kernel_flag(true);
+        attrs.set_exclude_hv_flag(true);
+        configure(&mut attrs, SIG_DATA);
+
+        install().unwrap();
+        register(SIG_DATA, on_hit).unwrap();
+        let fd = match unsafe { perf_event_open_fd(&mut attrs, 0, -1, -1, 0) } {
+            Ok(fd) => fd,
+            // Virtual machines often don't provide debug registers.
+            Err(err) if crate::test_util::unavailable(err.raw_os_error()) => {
+                unregister(SIG_DATA);
+                return;
+            }
+            Err(err) => panic!("{}", err),
+        };
+
+        ioctls::enable(fd.as_fd(), ioctls::Scope::Event).unwrap();
+        unsafe { std::ptr::write_volatile(&mut local, 1) };
+        ioctls::disable(fd.as_fd(), ioctls::Scope::Event).unwrap();
+        unregister(SIG_DATA);
+
+        assert_eq!(HITS.load(Ordering::SeqCst), 1);
+        assert_eq!(
+            LAST_TYPE.load(Ordering::SeqCst),
+            bindings::perf_type_id_PERF_TYPE_BREAKPOINT as u64
+        );
+        assert_eq!(
+            LAST_ADDR.load(Ordering::SeqCst),
+            &local as *const u64 as u64
+        );
+    }
+}
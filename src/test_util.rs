@@ -0,0 +1,66 @@
+//! Helpers shared by the unit tests.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Return true if `errno`, from a failed `perf_event_open`, means this
+/// machine can't provide the event, or we may not open it: no PMU, no
+/// debug registers, or a restrictive `perf_event_paranoid`.
+///
+/// Any other error, like `EINVAL`, means the test built a bad attr, and
+/// should fail the test rather than skip it.
+pub(crate) fn unavailable(errno: Option<i32>) -> bool {
+    matches!(
+        errno,
+        Some(libc::EACCES | libc::EPERM | libc::ENOENT | libc::ENODEV | libc::EOPNOTSUPP)
+    )
+}
+
+/// A directory tree in the system's temporary directory, for tests that
+/// need a fake sysfs, tracefs, or binary. The tree is removed when the
+/// `TempTree` is dropped, even if the test fails.
+pub(crate) struct TempTree {
+    root: PathBuf,
+}
+
+impl TempTree {
+    /// Create an empty tree. The directory's name includes `name`, the
+    /// process id, and a counter, so tests running in parallel, in this
+    /// process or another, never share one.
+    pub(crate) fn new(name: &str) -> TempTree {
+        static NEXT: AtomicUsize = AtomicUsize::new(0);
+        let root = std::env::temp_dir().join(format!(
+            "perf-{}-{}-{}",
+            name,
+            std::process::id(),
+            NEXT.fetch_add(1, Ordering::Relaxed)
+        ));
+        fs::create_dir_all(&root).unwrap();
+        TempTree { root }
+    }
+
+    /// Return the root of the tree.
+    pub(crate) fn path(&self) -> &Path {
+        &self.root
+    }
+
+    /// Return the path of `path`, relative to the root of the tree.
+    pub(crate) fn join<P: AsRef<Path>>(&self, path: P) -> PathBuf {
+        self.root.join(path)
+    }
+
+    /// Write `contents` to `path`, relative to the root of the tree,
+    /// creating any missing parent directories.
+    pub(crate) fn write<P: AsRef<Path>, C: AsRef<[u8]>>(&self, path: P, contents: C) {
+        let path = self.join(path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, contents).unwrap();
+    }
+}
+
+impl Drop for TempTree {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.root);
+    }
+}
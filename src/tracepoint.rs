@@ -0,0 +1,468 @@
+//! Tracepoint ids, formats, and raw sample decoding.
+//!
+//! A `PERF_TYPE_TRACEPOINT` event's `config` is the tracepoint's numeric id,
+//! which the kernel publishes in tracefs, along with a description of the
+//! data the tracepoint records:
+//!
+//! ```text
+//! /sys/kernel/tracing/events/sched/sched_switch/id
+//! /sys/kernel/tracing/events/sched/sched_switch/format
+//! ```
+//!
+//! The `format` file lists the fields of the record, which is what samples
+//! with `PERF_SAMPLE_RAW` carry:
+//!
+//! ```text
+//! name: sched_switch
+//! ID: 316
+//! format:
+//!         field:unsigned short common_type;       offset:0;       size:2; signed:0;
+//!         ...
+//!         field:char prev_comm[16];       offset:8;       size:16;        signed:0;
+//!         field:pid_t prev_pid;   offset:24;      size:4; signed:1;
+//! ```
+//!
+//! Variable-length fields are declared `__data_loc`: the record holds a
+//! 32-bit word whose low 16 bits give the data's offset from the start of the
+//! record and whose high 16 bits give its length. Newer kernels also use
+//! `__rel_loc`, whose offset is relative to the end of the word itself.
+//!
+//! A [`Tracefs`] finds tracepoints under a tracefs root, and a [`Format`]
+//! decodes raw sample data into named [`FieldValue`]s:
+//!
+//! ```no_run
+//! use perf_event_open_sys::tracepoint::Tracefs;
+//!
+//! let tracefs = Tracefs::find()?;
+//! let mut attrs = tracefs.attr("sched:sched_switch")?;
+//! let format = tracefs.format("sched:sched_switch")?;
+//! # let raw: &[u8] = &[];
+//! // ... open the event, and for each sample's `raw` data:
+//! for (name, value) in format.decode(raw)? {
+//!     println!("{} = {:?}", name, value);
+//! }
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use crate::bindings::{self, perf_event_attr};
+use crate::record::{Cursor, ParseError};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// The places tracefs is usually mounted, in order of preference.
+pub const TRACEFS_ROOTS: [&str; 2] = ["/sys/kernel/tracing", "/sys/kernel/debug/tracing"];
+
+/// A tracefs mount.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Tracefs {
+    root: PathBuf,
+}
+
+impl Tracefs {
+    /// Use the tracefs mounted at `root`, which may also be the `tracing`
+    /// directory of a debugfs mount.
+    pub fn new<P: Into<PathBuf>>(root: P) -> Tracefs {
+        Tracefs { root: root.into() }
+    }
+
+    /// Use the first of [`TRACEFS_ROOTS`] that has an `events` directory.
+    pub fn find() -> io::Result<Tracefs> {
+        TRACEFS_ROOTS
+            .iter()
+            .map(Tracefs::new)
+            .find(|tracefs| tracefs.root.join("events").is_dir())
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "tracefs is not mounted"))
+    }
+
+    /// Return the directory for the tracepoint `name`, given as
+    /// `"subsystem:event"`.
+    fn event_dir(&self, name: &str) -> io::Result<PathBuf> {
+        let (subsystem, event) = name.split_once(':').ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "tracepoint name {:?} is not of the form subsystem:event",
+                    name
+                ),
+            )
+        })?;
+        if [subsystem, event]
+            .iter()
+            .any(|part| part.is_empty() || part.contains('/') || part.starts_with('.'))
+        {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("bad tracepoint name {:?}", name),
+            ));
+        }
+        Ok(self.root.join("events").join(subsystem).join(event))
+    }
+
+    /// Return the id of the tracepoint `name`, like `"sched:sched_switch"`.
+    pub fn id(&self, name: &str) -> io::Result<u64> {
+        let text = fs::read_to_string(self.event_dir(name)?.join("id"))?;
+        text.trim()
+            .parse()
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }
+
+    /// Return the record format of the tracepoint `name`.
+    pub fn format(&self, name: &str) -> io::Result<Format> {
+        Format::parse(&fs::read_to_string(self.event_dir(name)?.join("format"))?)
+    }
+
+    /// Return a `perf_event_attr` for the tracepoint `name`.
+    ///
+    /// The result has `size`, `type_` and `config` set, and all other fields
+    /// zero. Set `PERF_SAMPLE_RAW` in `sample_type` to receive the records
+    /// [`Format::decode`] expects.
+    pub fn attr(&self, name: &str) -> io::Result<perf_event_attr> {
+        Ok(perf_event_attr {
+            size: std::mem::size_of::<perf_event_attr>() as u32,
+            type_: bindings::perf_type_id_PERF_TYPE_TRACEPOINT,
+            config: self.id(name)?,
+            ..Default::default()
+        })
+    }
+
+    /// Return the root directory.
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+}
+
+/// A tracepoint's record format, parsed from its `format` file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Format {
+    /// The tracepoint's name, without its subsystem.
+    pub name: String,
+
+    /// The tracepoint's id.
+    pub id: u64,
+
+    /// The record's fields, including the `common_*` fields every record
+    /// starts with.
+    pub fields: Vec<Field>,
+}
+
+/// How a field's data is stored.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FieldKind {
+    /// A single integer.
+    Scalar,
+
+    /// A fixed-length array of `len` elements, stored in place.
+    Array { len: usize },
+
+    /// A `__data_loc` field: a 32-bit word giving the offset from the start
+    /// of the record and length of the data.
+    DataLoc,
+
+    /// A `__rel_loc` field: like `DataLoc`, but the offset is relative to the
+    /// end of the word.
+    RelLoc,
+}
+
+/// A field of a tracepoint record.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Field {
+    pub name: String,
+
+    /// The field's C type, without the array length or `__data_loc`, like
+    /// `unsigned short`, `char` or `char[]`.
+    pub c_type: String,
+
+    pub kind: FieldKind,
+    pub offset: usize,
+    pub size: usize,
+    pub signed: bool,
+}
+
+impl Field {
+    /// Return true if this field holds a string: a `char` array, fixed or
+    /// variable.
+    pub fn is_string(&self) -> bool {
+        let element = self.c_type.trim_end_matches("[]").trim();
+        element == "char" && self.kind != FieldKind::Scalar
+    }
+
+    /// Decode this field's value from the record `raw`.
+    pub fn decode<'a>(&self, raw: &'a [u8]) -> Result<FieldValue<'a>, ParseError> {
+        let bytes = slice(raw, self.offset, self.size)?;
+        let data = match self.kind {
+            FieldKind::Scalar => return Ok(self.integer(bytes)),
+            FieldKind::Array { len } => {
+                let element = self.size.checked_div(len).unwrap_or(0);
+                if !self.is_string() && element > 0 && element * len == self.size {
+                    let values = bytes.chunks(element).map(|b| self.integer(b)).collect();
+                    return Ok(FieldValue::Array(values));
+                }
+                bytes
+            }
+            FieldKind::DataLoc | FieldKind::RelLoc => {
+                let word = Cursor::new(bytes).u32()?;
+                let mut offset = (word & 0xffff) as usize;
+                if self.kind == FieldKind::RelLoc {
+                    offset += self.offset + self.size;
+                }
+                slice(raw, offset, (word >> 16) as usize)?
+            }
+        };
+        if self.is_string() {
+            let len = data.iter().position(|&b| b == 0).unwrap_or(data.len());
+            Ok(FieldValue::Str(&data[..len]))
+        } else {
+            Ok(FieldValue::Bytes(data))
+        }
+    }
+
+    fn integer(&self, bytes: &[u8]) -> FieldValue<'static> {
+        let mut buf = [0_u8; 8];
+        let len = bytes.len().min(8);
+        let negative = self.signed && len > 0 && bytes[ne_msb(len)] & 0x80 != 0;
+        if cfg!(target_endian = "little") {
+            buf[..len].copy_from_slice(&bytes[..len]);
+            if negative {
+                buf[len..].iter_mut().for_each(|b| *b = 0xff);
+            }
+        } else {
+            buf[8 - len..].copy_from_slice(&bytes[..len]);
+            if negative {
+                buf[..8 - len].iter_mut().for_each(|b| *b = 0xff);
+            }
+        }
+        let value = u64::from_ne_bytes(buf);
+        if self.signed {
+            FieldValue::Signed(value as i64)
+        } else {
+            FieldValue::Unsigned(value)
+        }
+    }
+}
+
+/// Return the index of the most significant byte of a native-endian integer
+/// of `len` bytes.
+fn ne_msb(len: usize) -> usize {
+    if cfg!(target_endian = "little") {
+        len - 1
+    } else {
+        0
+    }
+}
+
+fn slice(raw: &[u8], offset: usize, len: usize) -> Result<&[u8], ParseError> {
+    raw.get(offset..)
+        .and_then(|rest| rest.get(..len))
+        .ok_or(ParseError::Truncated)
+}
+
+/// A decoded tracepoint field value.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FieldValue<'a> {
+    Signed(i64),
+    Unsigned(u64),
+
+    /// A string, without its terminating NUL.
+    Str(&'a [u8]),
+
+    /// A fixed-length array of integers.
+    Array(Vec<FieldValue<'a>>),
+
+    /// Variable-length data that isn't a string.
+    Bytes(&'a [u8]),
+}
+
+impl Format {
+    /// Parse the contents of a tracepoint's `format` file.
+    pub fn parse(text: &str) -> io::Result<Format> {
+        let invalid = |what: String| io::Error::new(io::ErrorKind::InvalidData, what);
+        let mut name = None;
+        let mut id = None;
+        let mut fields = vec![];
+
+        for line in text.lines().map(str::trim) {
+            if let Some(rest) = line.strip_prefix("name:") {
+                name = Some(rest.trim().to_string());
+            } else if let Some(rest) = line.strip_prefix("ID:") {
+                id = Some(rest.trim().parse().map_err(|_| invalid(line.to_string()))?);
+            } else if line.starts_with("field:") {
+                fields.push(parse_field(line).ok_or_else(|| invalid(line.to_string()))?);
+            }
+        }
+
+        Ok(Format {
+            name: name.ok_or_else(|| invalid("format has no name".to_string()))?,
+            id: id.ok_or_else(|| invalid("format has no ID".to_string()))?,
+            fields,
+        })
+    }
+
+    /// Return the field named `name`.
+    pub fn field(&self, name: &str) -> Option<&Field> {
+        self.fields.iter().find(|field| field.name == name)
+    }
+
+    /// Decode every field of the record `raw`, the `raw` data of a
+    /// `PERF_SAMPLE_RAW` sample.
+    pub fn decode<'a>(
+        &'a self,
+        raw: &'a [u8],
+    ) -> Result<Vec<(&'a str, FieldValue<'a>)>, ParseError> {
+        self.fields
+            .iter()
+            .map(|field| Ok((field.name.as_str(), field.decode(raw)?)))
+            .collect()
+    }
+}
+
+/// Parse a line like
+/// `field:char prev_comm[16]; offset:8; size:16; signed:0;`.
+fn parse_field(line: &str) -> Option<Field> {
+    let mut decl = None;
+    let mut offset = None;
+    let mut size = None;
+    let mut signed = false;
+    for part in line.split(';').map(str::trim).filter(|p| !p.is_empty()) {
+        let (key, value) = part.split_once(':')?;
+        match key {
+            "field" => decl = Some(value.trim()),
+            "offset" => offset = Some(value.trim().parse().ok()?),
+            "size" => size = Some(value.trim().parse().ok()?),
+            "signed" => signed = value.trim() == "1",
+            _ => {}
+        }
+    }
+
+    let decl = decl?;
+    let (decl, loc) = if let Some(rest) = decl.strip_prefix("__data_loc ") {
+        (rest, Some(FieldKind::DataLoc))
+    } else if let Some(rest) = decl.strip_prefix("__rel_loc ") {
+        (rest, Some(FieldKind::RelLoc))
+    } else {
+        (decl, None)
+    };
+    let split = decl.rfind(|c: char| c.is_whitespace() || c == '*')?;
+    let (c_type, name) = (decl[..=split].trim(), decl[split + 1..].trim());
+    let (name, kind) = match (name.split_once('['), loc) {
+        (_, Some(loc)) => (name, loc),
+        (Some((name, len)), None) => {
+            let len = len.strip_suffix(']')?.trim();
+            // Some array lengths are symbolic; treat them as unknown.
+            let len = len.parse().unwrap_or(0);
+            (name, FieldKind::Array { len })
+        }
+        (None, None) => (name, FieldKind::Scalar),
+    };
+    if name.is_empty() {
+        return None;
+    }
+
+    Some(Field {
+        name: name.to_string(),
+        c_type: c_type.to_string(),
+        kind,
+        offset: offset?,
+        size: size?,
+        signed,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCHED_SWITCH: &str = "\
+name: sched_switch
+ID: 316
+format:
+\tfield:unsigned short common_type;\toffset:0;\tsize:2;\tsigned:0;
+\tfield:unsigned char common_flags;\toffset:2;\tsize:1;\tsigned:0;
+\tfield:unsigned char common_preempt_count;\toffset:3;\tsize:1;\tsigned:0;
+\tfield:int common_pid;\toffset:4;\tsize:4;\tsigned:1;
+
+\tfield:char prev_comm[16];\toffset:8;\tsize:16;\tsigned:0;
+\tfield:pid_t prev_pid;\toffset:24;\tsize:4;\tsigned:1;
+\tfield:int prev_prio;\toffset:28;\tsize:4;\tsigned:1;
+\tfield:__data_loc char[] filename;\toffset:32;\tsize:4;\tsigned:0;
+\tfield:u16 counts[2];\toffset:36;\tsize:4;\tsigned:0;
+\tfield:__rel_loc u8[] blob;\toffset:40;\tsize:4;\tsigned:0;
+
+print fmt: \"prev_comm=%s prev_pid=%d\", REC->prev_comm, REC->prev_pid
+";
+
+    #[test]
+    fn parse_format() {
+        let format = Format::parse(SCHED_SWITCH).unwrap();
+        assert_eq!(format.name, "sched_switch");
+        assert_eq!(format.id, 316);
+        assert_eq!(format.fields.len(), 10);
+
+        let comm = format.field("prev_comm").unwrap();
+        assert_eq!(comm.c_type, "char");
+        assert_eq!(comm.kind, FieldKind::Array { len: 16 });
+        assert!(comm.is_string());
+
+        let pid = format.field("common_pid").unwrap();
+        assert_eq!((pid.offset, pid.size, pid.signed), (4, 4, true));
+
+        let filename = format.field("filename").unwrap();
+        assert_eq!(filename.kind, FieldKind::DataLoc);
+        assert_eq!(filename.c_type, "char[]");
+        assert!(filename.is_string());
+        assert_eq!(format.field("blob").unwrap().kind, FieldKind::RelLoc);
+    }
+
+    #[test]
+    fn decode_record() {
+        let format = Format::parse(SCHED_SWITCH).unwrap();
+
+        let mut raw = vec![];
+        raw.extend(316_u16.to_ne_bytes());
+        raw.extend([1_u8, 2]);
+        raw.extend(1234_i32.to_ne_bytes());
+        raw.extend(b"bash\0\0\0\0\0\0\0\0\0\0\0\0");
+        raw.extend((-7_i32).to_ne_bytes());
+        raw.extend(120_i32.to_ne_bytes());
+        // filename: 6 bytes at offset 44.
+        raw.extend((6_u32 << 16 | 44).to_ne_bytes());
+        raw.extend(3_u16.to_ne_bytes());
+        raw.extend(4_u16.to_ne_bytes());
+        // blob: 2 bytes, 6 bytes past the end of its word.
+        raw.extend((2_u32 << 16 | 6).to_ne_bytes());
+        raw.extend(b"/bin/\0");
+        raw.extend([0xaa, 0xbb]);
+
+        let values = format.decode(&raw).unwrap();
+        let get = |name: &str| values.iter().find(|(n, _)| *n == name).unwrap().1.clone();
+        assert_eq!(get("common_type"), FieldValue::Unsigned(316));
+        assert_eq!(get("common_pid"), FieldValue::Signed(1234));
+        assert_eq!(get("prev_comm"), FieldValue::Str(b"bash"));
+        assert_eq!(get("prev_pid"), FieldValue::Signed(-7));
+        assert_eq!(get("filename"), FieldValue::Str(b"/bin/"));
+        assert_eq!(
+            get("counts"),
+            FieldValue::Array(vec![FieldValue::Unsigned(3), FieldValue::Unsigned(4)])
+        );
+        assert_eq!(get("blob"), FieldValue::Bytes(&[0xaa, 0xbb]));
+
+        assert_eq!(format.decode(&raw[..30]), Err(ParseError::Truncated));
+    }
+
+    #[test]
+    fn lookup() {
+        let root = crate::test_util::TempTree::new("tracefs");
+        root.write("events/sched/sched_switch/id", "316\n");
+        root.write("events/sched/sched_switch/format", SCHED_SWITCH);
+
+        let tracefs = Tracefs::new(root.path());
+        let attrs = tracefs.attr("sched:sched_switch").unwrap();
+        assert_eq!(attrs.type_, bindings::perf_type_id_PERF_TYPE_TRACEPOINT);
+        assert_eq!(attrs.config, 316);
+        assert_eq!(tracefs.format("sched:sched_switch").unwrap().id, 316);
+        let missing = tracefs.id("sched:nonesuch");
+        assert_eq!(missing.unwrap_err().kind(), io::ErrorKind::NotFound);
+        let bad = tracefs.id("sched_switch");
+        assert_eq!(bad.unwrap_err().kind(), io::ErrorKind::InvalidInput);
+    }
+}